@@ -0,0 +1,230 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use colored::Colorize;
+use inquire::Confirm;
+
+use crate::config::app_config::{
+    find_key, read_config, read_layer, ConfigKey, LayeredConfig, Origin, CONFIG_KEYS,
+    PROJECT_CONFIG_FILE,
+};
+use crate::config::migrations::CONFIG_VERSION;
+use crate::config::zuko_context::{write_atomically, ZukoContext};
+
+/// Prints the value in effect, whichever layer it comes from.
+pub fn get(config: &LayeredConfig, name: &str) {
+    match find_key(name) {
+        Result::Ok(key) => println!("{}", key.get(&config.context)),
+        Result::Err(e) => eprintln!("{}", e),
+    }
+}
+
+pub fn set(config: &LayeredConfig, name: &str, value: &str, project: bool) {
+    if project {
+        let path = project_file(config);
+        update_project_file(&path, name, |layer, key| {
+            let mut context = ZukoContext::default();
+            key.set(&mut context, value)?;
+            let value = key
+                .toml_value(&context)
+                .ok_or_else(|| format!("{} can't be written to {}", key.name, PROJECT_CONFIG_FILE))?;
+            layer.insert(key.name.to_string(), value);
+            Ok(())
+        });
+        return;
+    }
+
+    let Some(mut context) = load_global(config) else {
+        return;
+    };
+    let result = find_key(name).and_then(|key| key.set(&mut context, value));
+    if let Result::Err(e) = result {
+        eprintln!("Failed to set {}: {}", name, e);
+        return;
+    }
+    if let Err(e) = context.save_to_file() {
+        eprintln!("Failed to save the config: {}", e);
+    }
+}
+
+pub fn unset(config: &LayeredConfig, name: &str, project: bool) {
+    if project {
+        let path = project_file(config);
+        update_project_file(&path, name, |layer, key| {
+            layer.remove(key.name);
+            Ok(())
+        });
+        return;
+    }
+
+    let Some(mut context) = load_global(config) else {
+        return;
+    };
+    let result = find_key(name).and_then(|key| key.reset(&mut context));
+    if let Result::Err(e) = result {
+        eprintln!("Failed to unset {}: {}", name, e);
+        return;
+    }
+    if let Err(e) = context.save_to_file() {
+        eprintln!("Failed to save the config: {}", e);
+    }
+}
+
+/// Prints every setting in effect, and with `show_origin` the layer it comes from.
+pub fn list(config: &LayeredConfig, show_origin: bool) {
+    for key in CONFIG_KEYS {
+        let value = key.get(&config.context);
+        if show_origin {
+            let origin = config.origins.get(key.name).unwrap_or(&Origin::Default);
+            println!("{}\t{} = {}", origin.to_string().dimmed(), key.name, value);
+        } else {
+            println!("{} = {}  {}", key.name, value, format!("# {}", key.description).dimmed());
+        }
+    }
+}
+
+pub fn path(config: &LayeredConfig, project: bool) {
+    if !project {
+        println!("{}", config.context.config_path().display());
+        return;
+    }
+    match &config.project_file {
+        Some(path) => println!("{}", path.display()),
+        None => eprintln!("No {} in this directory or above it", PROJECT_CONFIG_FILE),
+    }
+}
+
+/// Opens the config in `$VISUAL` or `$EDITOR`, and puts the previous one back if
+/// the edited config isn't valid and isn't fixed.
+pub fn edit(config: &LayeredConfig, project: bool) {
+    let path = if project { project_file(config) } else { config.context.config_path() };
+    let write_new = || match project {
+        true => fs::write(&path, ""),
+        false => ZukoContext::default().save_to_file(),
+    };
+    if !path.exists()
+        && let Err(e) = write_new()
+    {
+        eprintln!("Failed to write the config: {}", e);
+        return;
+    }
+    let original = match fs::read_to_string(&path) {
+        Result::Ok(text) => text,
+        Result::Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            return;
+        }
+    };
+
+    loop {
+        if let Err(e) = open_editor(&path) {
+            eprintln!("Failed to open the editor: {}", e);
+            return;
+        }
+        let result = match project {
+            true => read_layer(&path, false).map(|_| ()),
+            false => read_config(&path).map(|_| ()),
+        };
+        let error = match result {
+            Result::Ok(()) => {
+                println!("✅ Saved config to {}", path.display());
+                return;
+            }
+            Result::Err(e) => e,
+        };
+
+        eprintln!("The config isn't valid: {}", error);
+        let again = Confirm::new("Edit it again?")
+            .with_default(true)
+            .prompt()
+            .unwrap_or(false);
+        if !again {
+            // the rejected edit is left in the backup
+            match write_atomically(&path, &original) {
+                Result::Ok(()) => eprintln!("Kept the previous config, your changes are in {}.bak", path.display()),
+                Result::Err(e) => eprintln!("Failed to restore the previous config: {}", e),
+            }
+            return;
+        }
+    }
+}
+
+/// The global config as it is on disk, without the layers above it. Changes
+/// are refused while the file is broken so they don't overwrite it with defaults.
+pub fn load_global(config: &LayeredConfig) -> Option<ZukoContext> {
+    let path = config.context.config_path();
+    if !path.exists() {
+        return Some(ZukoContext::default());
+    }
+    match read_config(&path) {
+        Result::Ok(context) => Some(context),
+        Result::Err(e) => {
+            eprintln!("{} isn't valid: {}", path.display(), e);
+            eprintln!("Fix it with `zuko config edit`");
+            None
+        }
+    }
+}
+
+/// The project's `.zuko.toml`, or a new one in the current directory.
+fn project_file(config: &LayeredConfig) -> PathBuf {
+    config.project_file.clone().unwrap_or_else(|| {
+        std::env::current_dir()
+            .unwrap_or_else(|_| PathBuf::from("."))
+            .join(PROJECT_CONFIG_FILE)
+    })
+}
+
+/// Changes the setting `name` in the project config at `path`, which only holds
+/// the settings the project overrides.
+fn update_project_file(
+    path: &Path,
+    name: &str,
+    update: impl FnOnce(&mut toml::Table, &ConfigKey) -> Result<(), String>,
+) {
+    let mut layer = if path.exists() {
+        match read_layer(path, false) {
+            Result::Ok(layer) => layer,
+            Result::Err(e) => {
+                eprintln!("{} isn't valid: {}", path.display(), e);
+                eprintln!("Fix it with `zuko config edit --project`");
+                return;
+            }
+        }
+    } else {
+        toml::Table::new()
+    };
+
+    let result = find_key(name).and_then(|key| update(&mut layer, key));
+    if let Result::Err(e) = result {
+        eprintln!("Failed to change {}: {}", name, e);
+        return;
+    }
+
+    layer.insert("config_version".to_string(), i64::from(CONFIG_VERSION).into());
+    let written = toml::to_string_pretty(&layer)
+        .map_err(|e| e.to_string())
+        .and_then(|text| write_atomically(path, &text).map_err(|e| e.to_string()));
+    match written {
+        Result::Ok(()) => println!("✅ Saved config to {}", path.display()),
+        Result::Err(e) => eprintln!("Failed to save the config: {}", e),
+    }
+}
+
+fn open_editor(path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let editor = ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    let mut words = editor.split_whitespace();
+    let status = Command::new(words.next().unwrap_or("vi"))
+        .args(words)
+        .arg(path)
+        .status()?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", editor, status).into());
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use std::fs::File;
+use std::io::{stdout, BufWriter};
+use std::path::PathBuf;
+
+use crate::db::zuko_cli::{get_all_topics, get_questions_of_every_source};
+use crate::db::zuko_user::get_all_progress;
+use crate::types::{
+    DifficultyFilter, ProgressStatus, QuestionSummary, SourceFilter, TopicMatch, TopicSelection,
+};
+use crate::utils::problem_export::{write_export, ExportFormat, ExportRow};
+use crate::utils::question_index::QuestionIndex;
+
+pub async fn execute(
+    topic_slugs: Vec<String>,
+    topic_match: TopicMatch,
+    difficulty: Option<String>,
+    solved: bool,
+    source: Option<SourceFilter>,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+) {
+    let questions = match get_questions_of_every_source().await {
+        Result::Ok(qs) => qs,
+        Result::Err(e) => {
+            eprintln!("Failed to get questions from the database: {}", e);
+            return;
+        }
+    };
+
+    let progress = match get_all_progress().await {
+        Result::Ok(p) => p,
+        Result::Err(e) => {
+            eprintln!("Failed to get progress from the database: {}", e);
+            return;
+        }
+    };
+
+    let topics = match get_all_topics().await {
+        Result::Ok(ts) => ts,
+        Result::Err(e) => {
+            eprintln!("Failed to get topics from the database: {}", e);
+            return;
+        }
+    };
+
+    // the same filters as `zuko list`, applied the same way
+    let selection = TopicSelection::from_slugs(&topic_slugs, topic_match, &topics);
+    let difficulty = difficulty
+        .and_then(|d| d.parse::<DifficultyFilter>().ok())
+        .unwrap_or_else(DifficultyFilter::default);
+
+    let source = source.unwrap_or_default();
+    if let SourceFilter::Specific(wanted) = &source
+        && !questions.iter().any(|q| q.source == *wanted)
+    {
+        eprintln!("No questions found from source '{}'", source);
+        return;
+    }
+
+    let summaries: Vec<QuestionSummary> = questions.iter().map(QuestionSummary::from).collect();
+    let rows: Vec<ExportRow> = QuestionIndex::new(&summaries)
+        .filter(&selection, difficulty, &source)
+        .into_iter()
+        .map(|i| ExportRow::new(&questions[i], progress.get(&questions[i].title_slug)))
+        .filter(|row| !solved || row.status() == ProgressStatus::Solved)
+        .collect();
+
+    let result = match &output {
+        Some(path) => File::create(path)
+            .map_err(|e| e.into())
+            .and_then(|file| write_export(&rows, format, BufWriter::new(file))),
+        None => write_export(&rows, format, stdout().lock()),
+    };
+
+    match (result, output) {
+        (Result::Err(e), _) => eprintln!("Failed to export questions: {}", e),
+        (Result::Ok(()), Some(path)) => println!("Exported {} questions to {}", rows.len(), path.display()),
+        (Result::Ok(()), None) => {}
+    }
+}
@@ -0,0 +1,98 @@
+use std::path::PathBuf;
+
+use crate::db::local_questions::upsert_local_questions;
+use crate::db::packs::get_pack_question_summaries;
+use crate::db::search_index::rebuild_search_index;
+use crate::db::zuko_cli::get_synced_question_slugs;
+use crate::db::zuko_user::restore_progress;
+use crate::types::{Progress, Question};
+use crate::utils::problem_import::{read_problem_file, validate_questions, ImportFormat};
+
+pub async fn execute(file: PathBuf, format: Option<ImportFormat>) {
+    let Some(format) = format.or_else(|| ImportFormat::from_path(&file)) else {
+        eprintln!(
+            "Can't tell the format of {}, pass --format csv|json|md",
+            file.display()
+        );
+        return;
+    };
+
+    let rows = match read_problem_file(&file, format) {
+        Result::Ok(rows) => rows,
+        Result::Err(e) => {
+            eprintln!("Failed to read {}: {}", file.display(), e);
+            return;
+        }
+    };
+
+    if rows.is_empty() {
+        eprintln!("No questions found in {}", file.display());
+        return;
+    }
+
+    let mut reserved_slugs = match get_synced_question_slugs().await {
+        Result::Ok(slugs) => slugs,
+        Result::Err(e) => {
+            eprintln!("Failed to get questions from the database: {}", e);
+            return;
+        }
+    };
+    // imports would hide pack questions of the same slug
+    match get_pack_question_summaries().await {
+        Result::Ok(qs) => reserved_slugs.extend(qs.into_iter().map(|q| q.title_slug)),
+        Result::Err(e) => {
+            eprintln!("Failed to get pack questions: {}", e);
+            return;
+        }
+    }
+
+    // an export has every question in it; those already synced or from a pack
+    // are left as they are and only get their progress back
+    let progress: Vec<Progress> = rows
+        .iter()
+        .filter(|row| row.progress.is_set())
+        .map(|row| Progress {
+            title_slug: row.question.title_slug.clone(),
+            status: row.progress.status.unwrap_or_default(),
+            bookmarked: row.progress.bookmarked.unwrap_or(false),
+            last_solved_at: row.progress.last_solved_at.clone(),
+        })
+        .collect();
+    let (mut questions, skipped): (Vec<Question>, Vec<Question>) = rows
+        .into_iter()
+        .map(|row| row.question)
+        .partition(|question| !reserved_slugs.contains(&question.title_slug));
+
+    // nothing is written unless the whole file is valid
+    let errors = validate_questions(&mut questions, &reserved_slugs);
+    if !errors.is_empty() {
+        eprintln!("{} is not valid, nothing was imported:", file.display());
+        for error in errors {
+            eprintln!("  {}", error);
+        }
+        return;
+    }
+
+    let source = file.canonicalize().unwrap_or(file);
+    match upsert_local_questions(&questions, &source.display().to_string()).await {
+        Result::Ok(count) => println!("Imported {} questions from {}", count, source.display()),
+        Result::Err(e) => {
+            eprintln!("Failed to import questions: {}", e);
+            return;
+        }
+    }
+    if !skipped.is_empty() {
+        println!("Skipped {} questions that are already synced or from a pack", skipped.len());
+    }
+    if !progress.is_empty() {
+        match restore_progress(&progress).await {
+            Result::Ok(()) => println!("Restored progress on {} questions", progress.len()),
+            Result::Err(e) => eprintln!("Failed to restore progress: {}", e),
+        }
+    }
+
+    // make the statements findable with `zuko search`
+    if let Err(e) = rebuild_search_index().await {
+        eprintln!("Failed to rebuild the search index: {}", e);
+    }
+}
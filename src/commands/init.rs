@@ -72,6 +72,6 @@ pub async fn execute(context: &mut ZukoContext) {
     // } 
 
     // sync the zuko database
-    crate::db::sync_zuko_db().await;
+    crate::commands::sync::execute().await;
     println!("Zuko initialized successfully!");
 }
\ No newline at end of file
@@ -1,16 +1,24 @@
 use crate::config::zuko_context::ZukoContext;
-use crate::db::zuko_cli::{get_all_questions, get_all_topics};
+use crate::db::zuko_cli::{get_all_question_summaries, get_all_topics};
+use crate::db::sync_history::get_last_sync;
+use crate::db::zuko_user::get_all_progress;
 
-use crate::types::{Topic, AppState};
+use crate::types::{AppState, DifficultyFilter, SourceFilter, TopicMatch, TopicSelection};
 use crate::ui::run_ui;
+use chrono::{DateTime, Local};
 
 pub async fn execute(
     _context: &ZukoContext,
-    topic_slug: Option<String>,
+    topic_slugs: Vec<String>,
+    topic_match: TopicMatch,
     difficulty: Option<String>,
-    _solved: bool,
+    solved: bool,
+    source: Option<SourceFilter>,
 ) {
-    let questions = match get_all_questions(topic_slug, difficulty).await {
+    // Every question is loaded once; the filters below are applied in memory so the
+    // UI can change them without going back to the database. Statements are
+    // fetched later, only for the questions that get previewed.
+    let questions = match get_all_question_summaries().await {
         Result::Ok(qs) => qs,
         Result::Err(e) => {
             eprintln!("Failed to get questions from the database: {}", e);
@@ -23,7 +31,7 @@ pub async fn execute(
         return;
     }
 
-    let mut topics = match get_all_topics().await {
+    let topics = match get_all_topics().await {
         Result::Ok(ts) => ts,
         Result::Err(e) => {
             eprintln!("Failed to get topics from the database: {}", e);
@@ -31,15 +39,54 @@ pub async fn execute(
         }
     };
 
-    let new_topic = Topic {
-        name: "All Topics".to_string(),
-        slug: "".to_string(),
+    let progress = match get_all_progress().await {
+        Result::Ok(p) => p,
+        Result::Err(e) => {
+            eprintln!("Failed to get progress from the database: {}", e);
+            return;
+        }
     };
 
-    topics.insert(0, new_topic);
+    // reflect the command line filters in the UI so they can be adjusted there
+    let selected_topics = TopicSelection::from_slugs(&topic_slugs, topic_match, &topics);
 
+    let mut app_state = AppState::new(questions, topics, progress);
+    app_state.selected_topics = selected_topics;
+    if let Some(selected) = difficulty.and_then(|d| d.parse::<DifficultyFilter>().ok()) {
+        app_state.selected_difficulty = selected;
+        app_state.selected_difficulty_index = app_state
+            .difficulties
+            .iter()
+            .position(|d| d.to_str() == selected.to_str())
+            .unwrap_or(0);
+    }
+    if let Some(selected) = source {
+        let Some(index) = app_state.sources.iter().position(|s| *s == selected) else {
+            eprintln!("No questions found from source '{}'", selected);
+            return;
+        };
+        app_state.selected_source = selected;
+        app_state.selected_source_index = index;
+    }
+    app_state.refresh_filter_scope();
 
-    let mut app_state = AppState::new(questions, topics);
+    // only shown in the footer, so a missing sync log isn't worth failing over
+    app_state.last_synced_at = get_last_sync()
+        .await
+        .ok()
+        .flatten()
+        .and_then(|entry| DateTime::parse_from_rfc3339(&entry.synced_at).ok())
+        .map(|synced_at| synced_at.with_timezone(&Local));
+
+    if app_state.filter_scope.is_empty() {
+        eprintln!("No questions found for the selected filters");
+        return;
+    }
+
+    // `--solved` is expressed through the search syntax so it can be edited in the UI
+    if solved {
+        app_state.query = "is:solved ".to_string();
+    }
 
    if let Err(e) = run_ui(&mut app_state).await {
         eprintln!("Failed to run UI: {}", e);
@@ -1,5 +1,11 @@
+pub mod export;
+pub mod import;
 pub mod init;
 pub mod list;
+pub mod pack;
+pub mod search;
+pub mod show;
 pub mod solve;
 pub mod config;
-pub mod sync;
\ No newline at end of file
+pub mod sync;
+pub mod whatsnew;
@@ -0,0 +1,153 @@
+use std::collections::HashSet;
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::db::local_questions::get_local_question_summaries;
+use crate::db::packs::{get_installed_packs, get_pack_question_summaries, install_pack, remove_pack};
+use crate::db::search_index::rebuild_search_index;
+use crate::db::zuko_cli::get_synced_question_slugs;
+use crate::types::QuestionSource;
+use crate::utils::problem_pack::{read_pack, validate_pack, ProblemPack};
+
+pub async fn install(path: &Path) {
+    let Some(pack) = read_and_validate(path).await else {
+        return;
+    };
+
+    match install_pack(&pack).await {
+        Result::Ok(previous) => {
+            let manifest = &pack.manifest;
+            match previous {
+                Some(old) if old == manifest.version => println!(
+                    "Reinstalled {} {} ({} questions)",
+                    manifest.name, manifest.version, pack.questions.len()
+                ),
+                Some(old) => println!(
+                    "Upgraded {} from {} to {} ({} questions)",
+                    manifest.name, old, manifest.version, pack.questions.len()
+                ),
+                None => println!(
+                    "Installed {} {} by {} ({} questions)",
+                    manifest.name, manifest.version, manifest.author, pack.questions.len()
+                ),
+            }
+        }
+        Result::Err(e) => {
+            eprintln!("Failed to install the pack: {}", e);
+            return;
+        }
+    }
+
+    if let Err(e) = rebuild_search_index().await {
+        eprintln!("Failed to rebuild the search index: {}", e);
+    }
+}
+
+pub async fn list() {
+    let packs = match get_installed_packs().await {
+        Result::Ok(packs) => packs,
+        Result::Err(e) => {
+            eprintln!("Failed to get installed packs: {}", e);
+            return;
+        }
+    };
+
+    if packs.is_empty() {
+        println!("No packs installed, add one with `zuko pack install <path>`");
+        return;
+    }
+
+    for pack in packs {
+        let installed_at = chrono::DateTime::parse_from_rfc3339(&pack.installed_at)
+            .map(|d| d.with_timezone(&chrono::Local).format("%Y-%m-%d").to_string())
+            .unwrap_or(pack.installed_at);
+        println!(
+            "{} {}  {} questions  by {}, {}  (installed {})",
+            pack.name.bold(),
+            pack.version,
+            pack.question_count,
+            pack.author,
+            pack.license,
+            installed_at
+        );
+        if let Some(description) = pack.description {
+            println!("    {}", description.dimmed());
+        }
+    }
+}
+
+pub async fn remove(name: &str) {
+    match remove_pack(name).await {
+        Result::Ok(true) => println!("Removed {}", name),
+        Result::Ok(false) => {
+            eprintln!("No pack named '{}' is installed", name);
+            return;
+        }
+        Result::Err(e) => {
+            eprintln!("Failed to remove the pack: {}", e);
+            return;
+        }
+    }
+
+    if let Err(e) = rebuild_search_index().await {
+        eprintln!("Failed to rebuild the search index: {}", e);
+    }
+}
+
+pub async fn validate(path: &Path) {
+    if let Some(pack) = read_and_validate(path).await {
+        println!(
+            "{} {} is valid ({} questions, {} topics)",
+            pack.manifest.name,
+            pack.manifest.version,
+            pack.questions.len(),
+            pack.topics.len()
+        );
+    }
+}
+
+/// Reads the pack at `path` and prints every problem found, returning the pack
+/// only if it can be installed as is.
+async fn read_and_validate(path: &Path) -> Option<ProblemPack> {
+    let mut pack = match read_pack(path) {
+        Result::Ok(pack) => pack,
+        Result::Err(e) => {
+            eprintln!("Failed to read {}: {}", path.display(), e);
+            return None;
+        }
+    };
+
+    let reserved = match reserved_slugs(&pack.manifest.name).await {
+        Result::Ok(slugs) => slugs,
+        Result::Err(e) => {
+            eprintln!("Failed to get questions from the database: {}", e);
+            return None;
+        }
+    };
+
+    let errors = validate_pack(&mut pack, &reserved);
+    if !errors.is_empty() {
+        eprintln!("{} is not a valid pack:", path.display());
+        for error in errors {
+            eprintln!("  {}", error);
+        }
+        return None;
+    }
+    Some(pack)
+}
+
+/// Slugs a pack may not reuse: synced and imported questions and those of other
+/// packs. The pack's own installed questions are left out so it can be upgraded.
+async fn reserved_slugs(pack_name: &str) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    let mut slugs = get_synced_question_slugs().await?;
+    slugs.extend(get_local_question_summaries().await?.into_iter().map(|q| q.title_slug));
+    slugs.extend(
+        get_pack_question_summaries()
+            .await?
+            .into_iter()
+            .filter(|q| q.source != QuestionSource::Pack(pack_name.to_string()))
+            .map(|q| q.title_slug),
+    );
+    Ok(slugs)
+}
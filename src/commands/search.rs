@@ -0,0 +1,41 @@
+use colored::Colorize;
+
+use crate::db::search_index::{search_question_content, SNIPPET_END, SNIPPET_START};
+
+pub async fn execute(query: String, limit: usize) {
+    let matches = match search_question_content(&query, limit).await {
+        Result::Ok(ms) => ms,
+        Result::Err(e) => {
+            eprintln!("Failed to search questions: {}", e);
+            return;
+        }
+    };
+
+    if matches.is_empty() {
+        eprintln!("No questions found");
+        return;
+    }
+
+    for (i, m) in matches.iter().enumerate() {
+        println!(
+            "{:>3}. {} {}",
+            i + 1,
+            m.title.bold(),
+            format!("({})", m.title_slug).dimmed()
+        );
+        println!("     {}\n", highlight_snippet(&m.snippet));
+    }
+}
+
+fn highlight_snippet(snippet: &str) -> String {
+    let mut out = String::new();
+    for (i, part) in snippet.split([SNIPPET_START, SNIPPET_END]).enumerate() {
+        // odd parts sit between a start and an end marker
+        if i % 2 == 1 {
+            out.push_str(&part.yellow().bold().to_string());
+        } else {
+            out.push_str(part);
+        }
+    }
+    out
+}
@@ -0,0 +1,245 @@
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+
+use ratatui::crossterm::style::{Attribute, ContentStyle};
+use ratatui::style::Modifier;
+use ratatui::text::Line;
+use serde::Serialize;
+
+use crate::db::zuko_cli::get_questions;
+use crate::types::{Example, Limit, Question, QuestionSource, SimilarQuestion, Topic};
+use crate::utils::complexity::suggested_complexity;
+use crate::utils::parse_html::{question_to_lines, question_to_markdown};
+
+/// Width text is laid out for when it isn't going to a terminal.
+const PIPED_WIDTH: usize = 80;
+
+/// Lines get hard to read past this width, however wide the terminal is.
+const MAX_WIDTH: usize = 100;
+
+/// Outputs of `zuko show`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ShowFormat {
+    /// Laid out like the preview, with colors on a terminal.
+    #[default]
+    Text,
+    Markdown,
+    Json,
+    /// The statement as stored, with the other sections added in the same markup.
+    Html,
+}
+
+impl FromStr for ShowFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "text" | "txt" => Ok(ShowFormat::Text),
+            "md" | "markdown" => Ok(ShowFormat::Markdown),
+            "json" => Ok(ShowFormat::Json),
+            "html" => Ok(ShowFormat::Html),
+            _ => Err(format!("Unsupported format '{}', expected text, md, json or html", s)),
+        }
+    }
+}
+
+/// The question with its lists as real JSON arrays, unlike the database columns.
+#[derive(Serialize)]
+struct ShownQuestion<'a> {
+    title: &'a str,
+    title_slug: &'a str,
+    difficulty: Option<&'a str>,
+    topics: &'a [Topic],
+    source: &'a QuestionSource,
+    content: &'a str,
+    hints: &'a [String],
+    examples: &'a [Example],
+    limits: &'a [Limit],
+    suggested_complexity: Option<&'static str>,
+    example_testcases: &'a [String],
+    similar_questions: &'a [SimilarQuestion],
+}
+
+pub async fn execute(title_slug: String, format: ShowFormat) {
+    let question = match get_questions(std::slice::from_ref(&title_slug)).await {
+        Result::Ok(mut questions) if !questions.is_empty() => questions.remove(0),
+        Result::Ok(_) => {
+            eprintln!("No question found with slug '{}'", title_slug);
+            return;
+        }
+        Result::Err(e) => {
+            eprintln!("Failed to get the question from the database: {}", e);
+            return;
+        }
+    };
+
+    let to_terminal = std::io::stdout().is_terminal();
+    let output = match format {
+        ShowFormat::Text => render_text(&question, to_terminal),
+        ShowFormat::Markdown => question_to_markdown(&question),
+        ShowFormat::Json => match serde_json::to_string_pretty(&shown_question(&question)) {
+            Result::Ok(json) => json + "\n",
+            Result::Err(e) => {
+                eprintln!("Failed to write the question as JSON: {}", e);
+                return;
+            }
+        },
+        ShowFormat::Html => render_html(&question),
+    };
+
+    if to_terminal {
+        page(&output);
+    } else {
+        write_stdout(&output);
+    }
+}
+
+fn shown_question(question: &Question) -> ShownQuestion<'_> {
+    let limits = question.limits();
+    ShownQuestion {
+        title: &question.title,
+        title_slug: &question.title_slug,
+        difficulty: question.difficulty.as_deref(),
+        topics: question.topic.as_deref().unwrap_or_default(),
+        source: &question.source,
+        content: &question.content,
+        hints: question.hints.as_deref().unwrap_or_default(),
+        examples: question.examples(),
+        suggested_complexity: suggested_complexity(limits),
+        limits,
+        example_testcases: question.example_testcase_list.as_deref().unwrap_or_default(),
+        similar_questions: question.similar_question_list.as_deref().unwrap_or_default(),
+    }
+}
+
+/// Lays the question out for the terminal, or as plain text when piped.
+fn render_text(question: &Question, to_terminal: bool) -> String {
+    let width = match crossterm::terminal::size() {
+        Result::Ok((columns, _)) if to_terminal => columns as usize,
+        _ => PIPED_WIDTH,
+    };
+    question_to_lines(question, width.min(MAX_WIDTH))
+        .iter()
+        .map(|line| line_to_string(line, to_terminal))
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+/// The text of `line`, with ANSI escapes for its styles when `styled`.
+fn line_to_string(line: &Line, styled: bool) -> String {
+    if !styled {
+        return line.spans.iter().map(|span| span.content.as_ref()).collect();
+    }
+    // wrapping splits text into a span per word, which would be escaped one by one
+    let mut runs: Vec<(ratatui::style::Style, String)> = Vec::new();
+    for span in &line.spans {
+        match runs.last_mut() {
+            Some((style, text)) if *style == span.style => text.push_str(&span.content),
+            _ => runs.push((span.style, span.content.to_string())),
+        }
+    }
+
+    runs.into_iter()
+        .map(|(span_style, text)| {
+            let mut style = ContentStyle::new();
+            style.foreground_color = span_style.fg.map(Into::into);
+            style.background_color = span_style.bg.map(Into::into);
+            for (modifier, attribute) in [
+                (Modifier::BOLD, Attribute::Bold),
+                (Modifier::DIM, Attribute::Dim),
+                (Modifier::ITALIC, Attribute::Italic),
+                (Modifier::UNDERLINED, Attribute::Underlined),
+                (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+            ] {
+                if span_style.add_modifier.contains(modifier) {
+                    style.attributes.set(attribute);
+                }
+            }
+            style.apply(text).to_string()
+        })
+        .collect()
+}
+
+fn render_html(question: &Question) -> String {
+    let mut html = format!("<h1>{}</h1>\n", html_escape::encode_text(&question.title));
+    let mut facts = Vec::new();
+    if let Some(difficulty) = &question.difficulty {
+        facts.push(format!("<strong>Difficulty:</strong> {}", html_escape::encode_text(difficulty)));
+    }
+    if let Some(topics) = question.topic.as_ref().filter(|topics| !topics.is_empty()) {
+        let names: Vec<&str> = topics.iter().map(|t| t.name.as_str()).collect();
+        facts.push(format!("<strong>Topics:</strong> {}", html_escape::encode_text(&names.join(", "))));
+    }
+    if !facts.is_empty() {
+        html.push_str(&format!("<p>{}</p>\n", facts.join(" · ")));
+    }
+    html.push_str(&question.content);
+    html.push('\n');
+
+    // hints are markup already, like the statement
+    if let Some(hints) = question.hints.as_ref().filter(|hints| !hints.is_empty()) {
+        html.push_str("<details>\n<summary>Hints</summary>\n<ol>\n");
+        for hint in hints {
+            html.push_str(&format!("<li>{}</li>\n", hint));
+        }
+        html.push_str("</ol>\n</details>\n");
+    }
+    if let Some(examples) = question.example_testcase_list.as_ref().filter(|e| !e.is_empty()) {
+        html.push_str("<h2>Example test cases</h2>\n");
+        for example in examples {
+            html.push_str(&format!("<pre>{}</pre>\n", html_escape::encode_text(example)));
+        }
+    }
+    if let Some(similar) = question.similar_question_list.as_ref().filter(|s| !s.is_empty()) {
+        html.push_str("<h2>Similar questions</h2>\n<ul>\n");
+        for q in similar {
+            html.push_str(&format!(
+                "<li>{} <code>{}</code></li>\n",
+                html_escape::encode_text(&q.title),
+                html_escape::encode_text(&q.title_slug)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+    html
+}
+
+/// Shows `output` through `$PAGER` (`less` by default), printing it directly if
+/// the pager can't be started.
+fn page(output: &str) {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| "less".to_string());
+    let mut words = pager.split_whitespace();
+    let mut command = Command::new(words.next().unwrap_or("less"));
+    command.args(words).stdin(Stdio::piped());
+    // like git: keep the colors, and don't page what fits on one screen
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+
+    match command.spawn() {
+        Result::Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // fails when the pager is quit before reading everything
+                let _ = stdin.write_all(output.as_bytes());
+            }
+            let _ = child.wait();
+        }
+        Result::Err(_) => write_stdout(output),
+    }
+}
+
+/// Writes `output` to stdout. A reader that stops early, like `head`, isn't an error.
+fn write_stdout(output: &str) {
+    let mut stdout = std::io::stdout().lock();
+    match stdout.write_all(output.as_bytes()).and_then(|_| stdout.flush()) {
+        Result::Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+            eprintln!("Failed to write the question: {}", e);
+        }
+        _ => {}
+    }
+}
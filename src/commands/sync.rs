@@ -1,6 +1,69 @@
-use crate::db::{sync_zuko_db};
+use colored::Colorize;
 
+use crate::db::search_index::rebuild_search_index;
+use crate::db::sync_zuko_db;
+use crate::db::zuko_cli::get_shadowed_questions;
+
+/// Slugs listed per kind of change before the rest are summarized as a count.
+const MAX_LISTED_CHANGES: usize = 10;
 
 pub async fn execute() {
-    sync_zuko_db().await;
-}
\ No newline at end of file
+    let report = match sync_zuko_db().await {
+        Result::Ok(report) => report,
+        Result::Err(e) => {
+            eprintln!("Failed to sync the zuko database: {}", e);
+            return;
+        }
+    };
+
+    match report.frame_no {
+        Some(frame_no) => println!(
+            "Synced {} frames from the remote database (replica at frame {})",
+            report.frames_synced, frame_no
+        ),
+        None => println!("Synced {} frames from the remote database", report.frames_synced),
+    }
+
+    let diff = &report.diff;
+    println!(
+        "{} added, {} modified, {} removed",
+        diff.added.len().to_string().green(),
+        diff.modified.len().to_string().yellow(),
+        diff.removed.len().to_string().red()
+    );
+    print_changes(&diff.added, "+".green().to_string());
+    print_changes(&diff.modified, "~".yellow().to_string());
+    print_changes(&diff.removed, "-".red().to_string());
+
+    // the synced set may now use slugs that were free when questions were imported or packed
+    match get_shadowed_questions().await {
+        Result::Ok(shadowed) if !shadowed.is_empty() => {
+            println!(
+                "{} questions are hidden by another question with the same slug:",
+                shadowed.len().to_string().yellow()
+            );
+            for question in shadowed.iter().take(MAX_LISTED_CHANGES) {
+                println!("  {} ({})", question.title_slug, question.source);
+            }
+            if shadowed.len() > MAX_LISTED_CHANGES {
+                println!("    … and {} more", shadowed.len() - MAX_LISTED_CHANGES);
+            }
+        }
+        Result::Ok(_) => {}
+        Result::Err(e) => eprintln!("Failed to check for slug collisions: {}", e),
+    }
+
+    match rebuild_search_index().await {
+        Result::Ok(count) => println!("Indexed {} questions for search", count),
+        Result::Err(e) => eprintln!("Failed to rebuild the search index: {}", e),
+    }
+}
+
+fn print_changes(title_slugs: &[String], marker: String) {
+    for slug in title_slugs.iter().take(MAX_LISTED_CHANGES) {
+        println!("  {} {}", marker, slug);
+    }
+    if title_slugs.len() > MAX_LISTED_CHANGES {
+        println!("    … and {} more", title_slugs.len() - MAX_LISTED_CHANGES);
+    }
+}
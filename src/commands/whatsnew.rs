@@ -0,0 +1,90 @@
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+use colored::Colorize;
+
+use crate::db::sync_history::{get_last_sync, get_questions_added_since};
+
+/// How far back `whatsnew` looks without `--since`.
+const DEFAULT_LOOKBACK_DAYS: i64 = 7;
+
+pub async fn execute(since: Option<String>) {
+    let since_date = match since {
+        Some(date) => match NaiveDate::parse_from_str(&date, "%Y-%m-%d") {
+            Result::Ok(d) => d,
+            Result::Err(_) => {
+                eprintln!("Invalid date '{}', expected YYYY-MM-DD", date);
+                return;
+            }
+        },
+        None => Local::now().date_naive() - Duration::days(DEFAULT_LOOKBACK_DAYS),
+    };
+
+    // dates are given in local time while the log is kept in UTC
+    let Some(since_utc) = Local
+        .from_local_datetime(&since_date.and_hms_opt(0, 0, 0).unwrap_or_default())
+        .earliest()
+        .map(|d| d.with_timezone(&Utc))
+    else {
+        eprintln!("Invalid date '{}'", since_date);
+        return;
+    };
+
+    match get_last_sync().await {
+        Result::Ok(Some(entry)) => {
+            let frame = entry.frame_no.map_or("-".to_string(), |f| f.to_string());
+            println!(
+                "Last sync: {}, {} frames up to frame {} ({} added, {} modified, {} removed)\n",
+                format_timestamp(&entry.synced_at),
+                entry.frames_synced,
+                frame,
+                entry.added,
+                entry.modified,
+                entry.removed
+            );
+        }
+        Result::Ok(None) => {
+            eprintln!("No syncs recorded yet, run `zuko sync` first");
+            return;
+        }
+        Result::Err(e) => {
+            eprintln!("Failed to read the sync log: {}", e);
+            return;
+        }
+    }
+
+    let questions = match get_questions_added_since(&since_utc.to_rfc3339()).await {
+        Result::Ok(qs) => qs,
+        Result::Err(e) => {
+            eprintln!("Failed to get new questions: {}", e);
+            return;
+        }
+    };
+
+    if questions.is_empty() {
+        println!("No new questions since {}", since_date);
+        return;
+    }
+
+    println!("{} new questions since {}:", questions.len(), since_date);
+    for question in &questions {
+        let difficulty = match question.difficulty.as_deref().map(str::to_uppercase).as_deref() {
+            Some("EASY") => "Easy".green(),
+            Some("MEDIUM") => "Medium".yellow(),
+            Some("HARD") => "Hard".red(),
+            _ => "-".normal(),
+        };
+        println!(
+            "  {}  {:<6}  {} {}",
+            format_timestamp(&question.first_seen_at).dimmed(),
+            difficulty,
+            question.title.bold(),
+            format!("({})", question.title_slug).dimmed()
+        );
+    }
+}
+
+/// Shows an RFC 3339 timestamp as a local date and time.
+fn format_timestamp(timestamp: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|d| d.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_else(|_| timestamp.to_string())
+}
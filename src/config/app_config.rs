@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::migrations::migrate;
+use crate::config::zuko_context::ZukoContext;
+use crate::types::DifficultyFilter;
+
+/// Languages zuko can set up solutions in.
+pub const LANGUAGES: [&str; 7] = ["java", "python", "rust", "javascript", "c++", "ruby", "go"];
+
+/// Parses and stores a value of a setting, or says why it isn't valid.
+type Setter = fn(&mut ZukoContext, &str) -> Result<(), String>;
+
+/// Checks a value already set in a [`ZukoContext`].
+type Check = fn(&ZukoContext) -> Result<(), String>;
+
+/// A setting of [`ZukoContext`] that `zuko config` can read and change.
+pub struct ConfigKey {
+    /// Dotted path of the setting in config.toml.
+    pub name: &'static str,
+    pub description: &'static str,
+    get: fn(&ZukoContext) -> String,
+    /// `None` for settings zuko manages itself.
+    set: Option<Setter>,
+    /// Checks a value only when it is set, not when a file is read, e.g. that a
+    /// directory exists, which can change after it was set.
+    check: Option<Check>,
+    /// Puts back the value from the defaults.
+    reset: fn(&mut ZukoContext, ZukoContext),
+}
+
+pub static CONFIG_KEYS: &[ConfigKey] = &[
+    ConfigKey {
+        name: "username",
+        description: "Your name",
+        get: |context| context.username.clone(),
+        set: Some(|context, value| {
+            context.username = value.trim().to_string();
+            Ok(())
+        }),
+        reset: |context, default| context.username = default.username,
+        check: None,
+    },
+    ConfigKey {
+        name: "project_name",
+        description: "Name of the practice project",
+        get: |context| context.project_name.clone(),
+        set: Some(|context, value| {
+            context.project_name = value.trim().to_string();
+            Ok(())
+        }),
+        reset: |context, default| context.project_name = default.project_name,
+        check: None,
+    },
+    ConfigKey {
+        name: "project_root",
+        description: "Directory solutions are written to",
+        get: |context| context.project_root.display().to_string(),
+        set: Some(|context, value| {
+            context.project_root = PathBuf::from(value.trim());
+            Ok(())
+        }),
+        reset: |context, default| context.project_root = default.project_root,
+        // a directory that was moved away is found again with `config set`
+        check: Some(|context| {
+            let path = &context.project_root;
+            if !path.as_os_str().is_empty() && !path.is_dir() {
+                return Err(format!("{} is not a directory", path.display()));
+            }
+            Ok(())
+        }),
+    },
+    ConfigKey {
+        name: "dirs.config",
+        description: "Directory holding config.toml, from $ZUKO_HOME or $XDG_CONFIG_HOME",
+        get: |context| context.paths.config_dir.display().to_string(),
+        set: None,
+        reset: |_, _| {},
+        check: None,
+    },
+    ConfigKey {
+        name: "dirs.data",
+        description: "Directory holding the databases, from $ZUKO_HOME or $XDG_DATA_HOME",
+        get: |context| context.paths.data_dir.display().to_string(),
+        set: None,
+        reset: |_, _| {},
+        check: None,
+    },
+    ConfigKey {
+        name: "preferred_language_list",
+        description: "Languages to set up solutions in, comma separated",
+        get: |context| context.preferred_language_list.join(","),
+        set: Some(|context, value| {
+            let languages: Vec<String> = value
+                .split(',')
+                .map(|language| language.trim().to_lowercase())
+                .filter(|language| !language.is_empty())
+                .collect();
+            if let Some(unknown) = languages.iter().find(|l| !LANGUAGES.contains(&l.as_str())) {
+                return Err(format!(
+                    "Unsupported language '{}', expected some of {}",
+                    unknown,
+                    LANGUAGES.join(", ")
+                ));
+            }
+            context.preferred_language_list = languages;
+            Ok(())
+        }),
+        reset: |context, default| context.preferred_language_list = default.preferred_language_list,
+        check: None,
+    },
+    ConfigKey {
+        name: "editor_cmd",
+        description: "Command solutions are opened with",
+        get: |context| context.editor_cmd.clone(),
+        set: Some(|context, value| {
+            if value.trim().is_empty() {
+                return Err("The editor command can't be empty".to_string());
+            }
+            context.editor_cmd = value.trim().to_string();
+            Ok(())
+        }),
+        reset: |context, default| context.editor_cmd = default.editor_cmd,
+        check: None,
+    },
+    ConfigKey {
+        name: "difficulty",
+        description: "Default difficulty: all, easy, medium or hard",
+        get: |context| context.difficulty.to_str().to_lowercase(),
+        set: Some(|context, value| {
+            context.difficulty = value.trim().parse::<DifficultyFilter>()?;
+            Ok(())
+        }),
+        reset: |context, default| context.difficulty = default.difficulty,
+        check: None,
+    },
+    ConfigKey {
+        name: "init_git",
+        description: "Whether to create a git repository for the project",
+        get: |context| context.init_git.to_string(),
+        set: Some(|context, value| {
+            context.init_git = parse_bool(value)?;
+            Ok(())
+        }),
+        reset: |context, default| context.init_git = default.init_git,
+        check: None,
+    },
+    ConfigKey {
+        name: "track_progress",
+        description: "Whether to track progress",
+        get: |context| context.track_progress.to_string(),
+        set: Some(|context, value| {
+            context.track_progress = parse_bool(value)?;
+            Ok(())
+        }),
+        reset: |context, default| context.track_progress = default.track_progress,
+        check: None,
+    },
+];
+
+impl ConfigKey {
+    pub fn get(&self, context: &ZukoContext) -> String {
+        (self.get)(context)
+    }
+
+    pub fn set(&self, context: &mut ZukoContext, value: &str) -> Result<(), String> {
+        self.parse(context, value)?;
+        match self.check {
+            Some(check) => check(context),
+            None => Ok(()),
+        }
+    }
+
+    /// Like [`ConfigKey::set`], without the checks for values being set now.
+    fn parse(&self, context: &mut ZukoContext, value: &str) -> Result<(), String> {
+        match self.set {
+            Some(set) => set(context, value),
+            None => Err(format!("{} is managed by zuko and can't be set", self.name)),
+        }
+    }
+
+    /// The variable overriding this setting, e.g. `ZUKO_EDITOR_CMD`.
+    pub fn environment_variable(&self) -> String {
+        format!("ZUKO_{}", self.name.replace('.', "_").to_uppercase())
+    }
+
+    /// The TOML value this setting has in `context`.
+    pub fn toml_value(&self, context: &ZukoContext) -> Option<toml::Value> {
+        toml::Table::try_from(context).ok()?.remove(self.name)
+    }
+
+    pub fn reset(&self, context: &mut ZukoContext) -> Result<(), String> {
+        if self.set.is_none() {
+            return Err(format!("{} is managed by zuko and can't be unset", self.name));
+        }
+        (self.reset)(context, ZukoContext::default());
+        Ok(())
+    }
+}
+
+pub fn find_key(name: &str) -> Result<&'static ConfigKey, String> {
+    CONFIG_KEYS.iter().find(|key| key.name == name).ok_or_else(|| {
+        let names: Vec<&str> = CONFIG_KEYS.iter().map(|key| key.name).collect();
+        format!("Unknown config key '{}', expected one of {}", name, names.join(", "))
+    })
+}
+
+/// Name of the project config, looked for in the current directory and its parents.
+pub const PROJECT_CONFIG_FILE: &str = ".zuko.toml";
+
+/// Where the value of a setting came from. Later layers override earlier ones.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Origin {
+    Default,
+    /// The global config, or a project's `.zuko.toml`.
+    File(PathBuf),
+    /// A `ZUKO_*` environment variable, by name.
+    Environment(String),
+    /// `--config key=value`.
+    Flag,
+}
+
+impl fmt::Display for Origin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Origin::Default => write!(f, "default"),
+            Origin::File(path) => write!(f, "{}", path.display()),
+            Origin::Environment(name) => write!(f, "${}", name),
+            Origin::Flag => write!(f, "--config"),
+        }
+    }
+}
+
+/// The config in effect: the defaults, overridden by the global config, the
+/// project's `.zuko.toml`, `ZUKO_*` environment variables and `--config` flags.
+pub struct LayeredConfig {
+    pub context: ZukoContext,
+    /// Where each setting came from, by key name.
+    pub origins: HashMap<&'static str, Origin>,
+    pub project_file: Option<PathBuf>,
+}
+
+impl LayeredConfig {
+    pub fn load(start_dir: &Path, overrides: &[String]) -> Result<Self, Box<dyn Error>> {
+        let default = ZukoContext::default();
+        let global_file = default.config_path();
+        let project_file = find_project_file(start_dir);
+
+        let mut table = toml::Table::try_from(&default)?;
+        let mut origins: HashMap<&'static str, Origin> =
+            CONFIG_KEYS.iter().map(|key| (key.name, Origin::Default)).collect();
+        let mut overlay = |layer: toml::Table, file: &Path| {
+            for (name, value) in layer {
+                if let Result::Ok(key) = find_key(&name) {
+                    origins.insert(key.name, Origin::File(file.to_path_buf()));
+                }
+                table.insert(name, value);
+            }
+        };
+        if global_file.exists() {
+            overlay(read_layer(&global_file, true)?, &global_file);
+        }
+        if let Some(project_file) = &project_file {
+            let mut layer = read_layer(project_file, false)?;
+            resolve_project_root(&mut layer, project_file);
+            overlay(layer, project_file);
+        }
+        let mut context = context_from_table(table)?;
+
+        for key in CONFIG_KEYS.iter().filter(|key| key.set.is_some()) {
+            let variable = key.environment_variable();
+            if let Result::Ok(value) = std::env::var(&variable) {
+                key.set(&mut context, &value)
+                    .map_err(|e| format!("Invalid {}: {}", variable, e))?;
+                origins.insert(key.name, Origin::Environment(variable));
+            }
+        }
+        for assignment in overrides {
+            let (name, value) = assignment
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value, got '{}'", assignment))?;
+            let key = find_key(name.trim())?;
+            key.set(&mut context, value)
+                .map_err(|e| format!("Invalid --config {}: {}", key.name, e))?;
+            origins.insert(key.name, Origin::Flag);
+        }
+
+        Ok(LayeredConfig { context, origins, project_file })
+    }
+}
+
+/// The closest `.zuko.toml` in `start_dir` or above it.
+pub fn find_project_file(start_dir: &Path) -> Option<PathBuf> {
+    start_dir
+        .ancestors()
+        .map(|dir| dir.join(PROJECT_CONFIG_FILE))
+        .find(|path| path.is_file())
+}
+
+/// Reads the global config at `path`, failing on anything `zuko config set` would
+/// reject, unlike [`ZukoContext::load_or_default`] which falls back to the defaults.
+pub fn read_config(path: &Path) -> Result<ZukoContext, Box<dyn Error>> {
+    let mut table = toml::Table::try_from(ZukoContext::default())?;
+    table.extend(read_layer(path, true)?);
+    context_from_table(table)
+}
+
+/// Reads a config file that may set only some keys, migrated to the current
+/// version. Errors point at the line of the setting they are about.
+pub fn read_layer(path: &Path, global: bool) -> Result<toml::Table, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    // toml's own errors already say where they are
+    let mut layer: toml::Table =
+        toml::from_str(&text).map_err(|e| format!("{}: {}", path.display(), e.to_string().trim_end()))?;
+    let at = |name: &str, message: String| match locate_key(&text, name) {
+        Some((line, column)) => format!("{}:{}:{}: {}", path.display(), line, column, message.trim_end()),
+        None => format!("{}: {}", path.display(), message.trim_end()),
+    };
+    migrate(&mut layer).map_err(|e| at("config_version", e))?;
+
+    for (name, value) in &layer {
+        let key = find_key(name).map_err(|e| at(name, e))?;
+        if key.set.is_none() {
+            return Err(at(name, format!("{} is managed by zuko and can't be set", name)).into());
+        }
+        // each value has to be valid on its own
+        let mut table = toml::Table::try_from(ZukoContext::default())?;
+        table.insert(name.clone(), value.clone());
+        if !global {
+            resolve_project_root(&mut table, path);
+        }
+        context_from_table(table).map_err(|e| at(name, e.to_string()))?;
+    }
+    Ok(layer)
+}
+
+/// The line and column where `name` is set in `text`, counting from 1.
+fn locate_key(text: &str, name: &str) -> Option<(usize, usize)> {
+    text.lines().enumerate().find_map(|(i, line)| {
+        let trimmed = line.trim_start();
+        let is_assignment = trimmed
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.trim_start().starts_with('='));
+        let is_table = trimmed.starts_with(&format!("[{}]", name));
+        (is_assignment || is_table).then(|| (i + 1, line.len() - trimmed.len() + 1))
+    })
+}
+
+/// A project is where its `.zuko.toml` is, and a `project_root` it sets is
+/// relative to that.
+fn resolve_project_root(table: &mut toml::Table, project_file: &Path) {
+    let project_dir = project_file.parent().unwrap_or(Path::new("."));
+    let root = match table.get("project_root").and_then(toml::Value::as_str) {
+        Some(root) if !root.is_empty() => project_dir.join(root),
+        _ => project_dir.to_path_buf(),
+    };
+    table.insert("project_root".to_string(), root.display().to_string().into());
+}
+
+fn context_from_table(table: toml::Table) -> Result<ZukoContext, Box<dyn Error>> {
+    let context: ZukoContext = table.try_into()?;
+    // each value has to be one that could have been set
+    for key in CONFIG_KEYS.iter().filter(|key| key.set.is_some()) {
+        key.parse(&mut context.clone(), &key.get(&context))
+            .map_err(|e| format!("Invalid {}: {}", key.name, e))?;
+    }
+    Ok(context)
+}
+
+fn parse_bool(value: &str) -> Result<bool, String> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "yes" | "on" | "1" => Ok(true),
+        "false" | "no" | "off" | "0" => Ok(false),
+        _ => Err(format!("Expected true or false, got '{}'", value.trim())),
+    }
+}
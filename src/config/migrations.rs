@@ -0,0 +1,50 @@
+/// Version of the config files this build writes.
+pub const CONFIG_VERSION: u32 = 2;
+
+/// Upgrades a config file by one version; the migration at index `i` takes a
+/// file from version `i` to `i + 1`.
+type Migration = fn(&mut toml::Table) -> Result<(), String>;
+
+const MIGRATIONS: [Migration; CONFIG_VERSION as usize] = [difficulty_as_name, drop_cli_dir];
+
+/// Brings a config file to [`CONFIG_VERSION`] and removes its `config_version`,
+/// so only settings are left. Files from before versioning count as version 0.
+pub fn migrate(table: &mut toml::Table) -> Result<(), String> {
+    let version = match table.remove("config_version") {
+        None => 0,
+        Some(toml::Value::Integer(version)) => u32::try_from(version)
+            .map_err(|_| format!("Invalid config_version {}", version))?,
+        Some(value) => return Err(format!("config_version should be a number, not {}", value)),
+    };
+    if version > CONFIG_VERSION {
+        return Err(format!(
+            "config_version {} is newer than this zuko supports ({}), please update zuko",
+            version, CONFIG_VERSION
+        ));
+    }
+    for migration in &MIGRATIONS[version as usize..] {
+        migration(table)?;
+    }
+    Ok(())
+}
+
+/// Version 1 writes the difficulty as `"easy"` rather than `{ Specific = "Easy" }`.
+fn difficulty_as_name(table: &mut toml::Table) -> Result<(), String> {
+    let name = match table.get("difficulty") {
+        None => return Ok(()),
+        Some(toml::Value::String(name)) => name.to_lowercase(),
+        Some(toml::Value::Table(specific)) => match specific.get("Specific") {
+            Some(toml::Value::String(name)) => name.to_lowercase(),
+            _ => return Err(format!("Unknown difficulty {}", toml::Value::Table(specific.clone()))),
+        },
+        Some(value) => return Err(format!("Unknown difficulty {}", value)),
+    };
+    table.insert("difficulty".to_string(), name.into());
+    Ok(())
+}
+
+/// Version 2 finds its directories from the environment, see `config::paths`.
+fn drop_cli_dir(table: &mut toml::Table) -> Result<(), String> {
+    table.remove("cli_dir");
+    Ok(())
+}
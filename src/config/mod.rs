@@ -1,3 +1,6 @@
+pub mod app_config;
+pub mod migrations;
+pub mod paths;
 pub mod zuko_context;
 pub mod db;
 pub mod ui;
\ No newline at end of file
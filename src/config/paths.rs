@@ -0,0 +1,135 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use dirs::home_dir;
+
+/// Puts all of zuko's files in one directory, e.g. for tests and containers.
+pub const ZUKO_HOME: &str = "ZUKO_HOME";
+
+/// Where zuko keeps its files, following the XDG base directories.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZukoPaths {
+    /// Holds config.toml.
+    pub config_dir: PathBuf,
+    /// Holds the databases.
+    pub data_dir: PathBuf,
+}
+
+impl Default for ZukoPaths {
+    fn default() -> Self {
+        Self::resolve()
+    }
+}
+
+impl ZukoPaths {
+    /// `$ZUKO_HOME` for everything when it is set, otherwise `zuko` in
+    /// `$XDG_CONFIG_HOME` and `$XDG_DATA_HOME`.
+    pub fn resolve() -> Self {
+        if let Some(home) = std::env::var_os(ZUKO_HOME).filter(|home| !home.is_empty()) {
+            let home = PathBuf::from(home);
+            return ZukoPaths {
+                config_dir: home.clone(),
+                data_dir: home,
+            };
+        }
+        ZukoPaths {
+            config_dir: base_dir("XDG_CONFIG_HOME", ".config").join("zuko"),
+            data_dir: base_dir("XDG_DATA_HOME", ".local/share").join("zuko"),
+        }
+    }
+
+    pub fn config_file(&self) -> PathBuf {
+        self.config_dir.join("config.toml")
+    }
+
+    pub fn db_dir(&self) -> PathBuf {
+        self.data_dir.join("db")
+    }
+}
+
+/// The base directory named by `variable`, or `default` under the home directory.
+/// Relative paths are ignored, as the spec says.
+fn base_dir(variable: &str, default: &str) -> PathBuf {
+    std::env::var_os(variable)
+        .map(PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .unwrap_or_else(|| home_dir().unwrap_or_else(|| PathBuf::from(".")).join(default))
+}
+
+/// Moves the config and databases out of `~/.zuko`, where they were kept before
+/// the XDG directories, unless there are already files in the new places.
+/// Returns the old directory when something was moved.
+pub fn move_legacy_home(paths: &ZukoPaths) -> io::Result<Option<PathBuf>> {
+    let Some(legacy) = home_dir().map(|home| home.join(".zuko")) else {
+        return Ok(None);
+    };
+    if !legacy.is_dir() || std::env::var_os(ZUKO_HOME).is_some_and(|home| !home.is_empty()) {
+        return Ok(None);
+    }
+
+    let mut moved = false;
+    let config = legacy.join("config.toml");
+    if config.is_file() && !paths.config_file().exists() {
+        fs::create_dir_all(&paths.config_dir)?;
+        move_path(&config, &paths.config_file())?;
+        let backup = legacy.join("config.toml.bak");
+        if backup.is_file() {
+            move_path(&backup, &paths.config_dir.join("config.toml.bak"))?;
+        }
+        moved = true;
+    }
+    let db_dir = legacy.join("db");
+    if db_dir.is_dir() && !paths.db_dir().exists() {
+        fs::create_dir_all(&paths.data_dir)?;
+        move_path(&db_dir, &paths.db_dir())?;
+        moved = true;
+    }
+
+    // only goes away once nothing else is left in it
+    let _ = fs::remove_dir(&legacy);
+    Ok(moved.then_some(legacy))
+}
+
+/// Renames `from` to `to`, copying instead when they are on different file systems.
+/// The copy is made next to `to` and renamed into place, so an interrupted move
+/// never leaves a partial `to` behind.
+fn move_path(from: &Path, to: &Path) -> io::Result<()> {
+    match fs::rename(from, to) {
+        Result::Ok(()) => return Ok(()),
+        Result::Err(e) if e.kind() != io::ErrorKind::CrossesDevices => return Err(e),
+        Result::Err(_) => {}
+    }
+
+    let mut name = to.file_name().unwrap_or_default().to_owned();
+    name.push(".moving");
+    let temporary = to.with_file_name(name);
+    remove_path(&temporary)?;
+    let copied = copy_path(from, &temporary).and_then(|_| fs::rename(&temporary, to));
+    if let Result::Err(e) = copied {
+        let _ = remove_path(&temporary);
+        return Err(e);
+    }
+    remove_path(from)
+}
+
+/// Removes a file or directory, if there is one.
+fn remove_path(path: &Path) -> io::Result<()> {
+    let removed = if path.is_dir() { fs::remove_dir_all(path) } else { fs::remove_file(path) };
+    match removed {
+        Result::Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(()),
+        removed => removed,
+    }
+}
+
+fn copy_path(from: &Path, to: &Path) -> io::Result<()> {
+    if !from.is_dir() {
+        return fs::copy(from, to).map(|_| ());
+    }
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        copy_path(&entry.path(), &to.join(entry.file_name()))?;
+    }
+    Ok(())
+}
@@ -13,6 +13,9 @@ pub const BLOCK_PADDING: Padding = Padding::new(2, 2, 2, 2);
 pub const HIGHLIGHT_SYMBOL: &str = "┃ ";
 
 pub const LIGHT_ORANGE: Color = Color::Rgb(255, 184, 106);
+pub const EASY_COLOR: Color = Color::Rgb(66, 190, 101);
+pub const MEDIUM_COLOR: Color = Color::Rgb(241, 194, 27);
+pub const HARD_COLOR: Color = Color::Rgb(238, 83, 96);
 
 // pub const BLOCK_MARGIN: Padding = Padding::new(2, 2, 2 , 2);
 // pub const BLUE: Color = Color::Rgb(120, 169, 255);
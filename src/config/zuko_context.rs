@@ -1,8 +1,10 @@
+use crate::config::app_config::{read_config, LANGUAGES};
+use crate::config::migrations::CONFIG_VERSION;
+use crate::config::paths::ZukoPaths;
 use crate::types::DifficultyFilter;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::path::{Path, PathBuf};
 use derive_builder::Builder;
-use dirs::home_dir; 
 use std::fs;
 use std::fs::create_dir_all;
 use std::io::Write;
@@ -11,11 +13,17 @@ use std::io::Write;
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[builder(default)]
 pub struct ZukoContext {
+    /// Version of the file layout, see `config::migrations`.
+    pub config_version: u32,
     pub project_root: PathBuf,
     pub project_name: String,
-    pub cli_dir: PathBuf,
+    /// Where the config and databases are, which depends on the environment
+    /// rather than on the config.
+    #[serde(skip)]
+    pub paths: ZukoPaths,
     pub preferred_language_list: Vec<String>,
     pub editor_cmd: String,
+    #[serde(with = "difficulty_name")]
     pub difficulty: DifficultyFilter,
     pub init_git: bool,
     pub track_progress: bool,
@@ -24,30 +32,12 @@ pub struct ZukoContext {
 
 impl Default for ZukoContext {
     fn default() -> Self {
-
-        let cli_dir = home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zuko");
-        
-
-        if !cli_dir.exists() {
-            create_dir_all(&cli_dir)
-                .expect("Failed to create .zuko directory");
-        }
-
         ZukoContext {
+            config_version: CONFIG_VERSION,
             project_root: PathBuf::new(),
             project_name: String::new(),
-            cli_dir,
-            preferred_language_list: vec![
-                "java".to_string(),
-                "python".to_string(),
-                "rust".to_string(),
-                "javascript".to_string(),
-                "c++".to_string(),
-                "ruby".to_string(),
-                "go".to_string(),
-            ],
+            paths: ZukoPaths::resolve(),
+            preferred_language_list: LANGUAGES.iter().map(|l| l.to_string()).collect(),
             editor_cmd: "code".to_string(),
             difficulty: DifficultyFilter::default(),
             init_git: true,
@@ -58,51 +48,88 @@ impl Default for ZukoContext {
 }
 
 impl ZukoContext {
+    pub fn config_path(&self) -> PathBuf {
+        self.paths.config_file()
+    }
+
     pub fn save_to_file(&self) -> std::io::Result<()> {
         // Serialize context to TOML
         let toml_string = toml::to_string_pretty(self)
             .expect("Failed to serialize ZukoContext");
 
-        // Ensure .zuko directory exists
-        if !self.cli_dir.exists() {
-            create_dir_all(&self.cli_dir)?;
+        // Ensure the config directory exists
+        if !self.paths.config_dir.exists() {
+            create_dir_all(&self.paths.config_dir)?;
         }
 
         // Define config path
-        let config_path = self.cli_dir.join("config.toml");
+        let config_path = self.config_path();
 
         // Write to config file
-        let mut file = fs::File::create(&config_path)?;
-        file.write_all(toml_string.as_bytes())?;
+        write_atomically(&config_path, &toml_string)?;
 
         println!("✅ Saved config to {}", config_path.display());
 
         Ok(())
     }
 
-    fn load_context_from_file(cli_dir: &Path) -> Option<ZukoContext> {
-        let config_path = cli_dir.join("config.toml");
-        let toml_str = fs::read_to_string(config_path).ok()?;
-        toml::from_str(&toml_str).ok()
+    fn load_context_from_file(paths: &ZukoPaths) -> Option<ZukoContext> {
+        let config_path = paths.config_file();
+        if !config_path.exists() {
+            return None;
+        }
+        match read_config(&config_path) {
+            Result::Ok(context) => Some(context),
+            Result::Err(e) => {
+                eprintln!("Failed to read the config, using the defaults: {}", e);
+                None
+            }
+        }
     }
 
     pub fn load_or_default() -> Self {
-        let cli_dir = home_dir()
-            .unwrap_or_else(|| PathBuf::from("."))
-            .join(".zuko");
-
-        if !cli_dir.exists() {
-            create_dir_all(&cli_dir).expect("Failed to create .zuko directory");
-        }
-
+        let paths = ZukoPaths::resolve();
 
-        if let Some(ctx) = Self::load_context_from_file(&cli_dir) {
+        if let Some(ctx) = Self::load_context_from_file(&paths) {
             return ctx;
         }
 
         ZukoContextBuilder::default()
-            .cli_dir(cli_dir)
+            .paths(paths)
             .build()
             .expect("Failed to build default context")
     }
-}
\ No newline at end of file
+}
+
+/// Replaces `path` with `contents` so it is never left half written: they go to a
+/// temporary file that is renamed over it, and the previous version is kept next
+/// to it as `<name>.bak`.
+pub fn write_atomically(path: &Path, contents: &str) -> std::io::Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let mut file = tempfile::NamedTempFile::new_in(dir)?;
+    file.write_all(contents.as_bytes())?;
+    file.as_file().sync_all()?;
+
+    if path.exists() {
+        let mut backup = path.as_os_str().to_owned();
+        backup.push(".bak");
+        fs::copy(path, backup)?;
+    }
+    file.persist(path).map_err(|e| e.error)?;
+    Ok(())
+}
+
+/// The difficulty by name, e.g. `"easy"`, as written since config version 1.
+mod difficulty_name {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(difficulty: &DifficultyFilter, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&difficulty.to_str().to_lowercase())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<DifficultyFilter, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::db::get_zuko_user_db;
+use crate::types::{Question, QuestionSummary, Topic};
+use libsql::{de, params, Value};
+use serde::Serialize;
+
+/// Questions imported by the user (see `zuko import`), in the same shape as the
+/// replica's `QuestionList` so both load into the same structs.
+const CREATE_LOCAL_QUESTION_TABLE: &str = "CREATE TABLE IF NOT EXISTS LocalQuestion (
+    title_slug TEXT PRIMARY KEY,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    difficulty TEXT,
+    topic TEXT,
+    hints TEXT,
+    example_testcase_list TEXT,
+    similar_question_list TEXT,
+    next_challenges TEXT,
+    imported_from TEXT,
+    imported_at TEXT NOT NULL
+)";
+
+const BASE_QUERY: &str = "SELECT title, title_slug, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges, 'imported' AS source FROM LocalQuestion";
+
+const SUMMARY_QUERY: &str = "SELECT title, title_slug, difficulty, topic, 'imported' AS source FROM LocalQuestion";
+
+pub async fn initialize_local_questions() -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    db.execute(CREATE_LOCAL_QUESTION_TABLE, ()).await?;
+    Ok(())
+}
+
+/// Inserts or replaces `questions` in one transaction, returning how many were written.
+pub async fn upsert_local_questions(
+    questions: &[Question],
+    imported_from: &str,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    let imported_at = chrono::Utc::now().to_rfc3339();
+
+    let tx = db.transaction().await?;
+    for question in questions {
+        tx.execute(
+            "INSERT INTO LocalQuestion (title_slug, title, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges, imported_from, imported_at)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+             ON CONFLICT(title_slug) DO UPDATE SET
+                title = excluded.title,
+                content = excluded.content,
+                difficulty = excluded.difficulty,
+                topic = excluded.topic,
+                hints = excluded.hints,
+                example_testcase_list = excluded.example_testcase_list,
+                similar_question_list = excluded.similar_question_list,
+                next_challenges = excluded.next_challenges,
+                imported_from = excluded.imported_from,
+                imported_at = excluded.imported_at",
+            params![
+                question.title_slug.as_str(),
+                question.title.as_str(),
+                question.content.as_str(),
+                question.difficulty.clone(),
+                to_json_column(&question.topic)?,
+                to_json_column(&question.hints)?,
+                to_json_column(&question.example_testcase_list)?,
+                to_json_column(&question.similar_question_list)?,
+                to_json_column(&question.next_challenges)?,
+                imported_from,
+                imported_at.as_str()
+            ],
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(questions.len())
+}
+
+/// Stores list columns as JSON text, the way `QuestionList` does.
+pub fn to_json_column<T: Serialize>(value: &Option<Vec<T>>) -> Result<Option<String>, serde_json::Error> {
+    value.as_ref().map(serde_json::to_string).transpose()
+}
+
+pub async fn get_local_questions() -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db.query(BASE_QUERY, ()).await?;
+
+    let mut questions = Vec::new();
+    while let Some(row) = rows.next().await? {
+        questions.push(de::from_row::<Question>(&row)?);
+    }
+    Ok(questions)
+}
+
+pub async fn get_local_question_summaries() -> Result<Vec<QuestionSummary>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db.query(SUMMARY_QUERY, ()).await?;
+
+    let mut questions = Vec::new();
+    while let Some(row) = rows.next().await? {
+        questions.push(de::from_row::<QuestionSummary>(&row)?);
+    }
+    Ok(questions)
+}
+
+/// Loads the imported questions among `title_slugs`; other slugs are skipped.
+pub async fn get_local_questions_by_slug(
+    title_slugs: &[String],
+) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    if title_slugs.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let db = get_zuko_user_db();
+
+    let placeholders = (1..=title_slugs.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let query = format!("{BASE_QUERY} WHERE title_slug IN ({placeholders})");
+    let values: Vec<Value> = title_slugs.iter().map(|s| Value::from(s.as_str())).collect();
+
+    let mut rows = db.query(&query, values).await?;
+
+    let mut questions = Vec::new();
+    while let Some(row) = rows.next().await? {
+        questions.push(de::from_row::<Question>(&row)?);
+    }
+    Ok(questions)
+}
+
+/// Topics used by imported questions, which the synced `Topics` table doesn't know about.
+pub async fn get_local_topics() -> Result<Vec<Topic>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db
+        .query(
+            "SELECT DISTINCT json_each.value ->> '$.name' AS name, json_each.value ->> '$.slug' AS slug
+             FROM LocalQuestion, json_each(LocalQuestion.topic)
+             ORDER BY name",
+            (),
+        )
+        .await?;
+
+    let mut topics = Vec::new();
+    while let Some(row) = rows.next().await? {
+        topics.push(de::from_row::<Topic>(&row)?);
+    }
+    Ok(topics)
+}
@@ -1,24 +1,52 @@
+pub mod local_questions;
+pub mod packs;
+pub mod search_index;
+pub mod sync_history;
 pub mod zuko_cli;
 pub mod zuko_user;
 // pub mod list;
 
 use libsql::{Connection, Database};
+use crate::types::{SyncDiff, SyncReport};
 use tokio::sync::OnceCell;
 
 pub static ZUKO_DATABASE: OnceCell<Database> = OnceCell::const_new(); 
 pub static ZUKO_DB: OnceCell<Connection> = OnceCell::const_new();
-// pub static ZUKO_USER_DB: OnceCell<Connection> = OnceCell::const_new();
+pub static ZUKO_USER_DB: OnceCell<Connection> = OnceCell::const_new();
 
 
 pub fn get_zuko_db() -> Connection {
     ZUKO_DB.get().expect("ZUKO_DB not initialized!").clone()
 }
 
-// pub fn get_zuko_user_db() -> Connection {
-//     ZUKO_USER_DB.get().expect("ZUKO_USER_DB not initialized!").clone()
-// }
+pub fn get_zuko_user_db() -> Connection {
+    ZUKO_USER_DB.get().expect("ZUKO_USER_DB not initialized!").clone()
+}
 
-pub async fn sync_zuko_db() {
+/// Pulls new frames from the remote database into the local replica and records
+/// which questions were added, modified or removed, see [`sync_history`].
+///
+/// Callers should rebuild the search index afterwards, see
+/// [`search_index::rebuild_search_index`].
+pub async fn sync_zuko_db() -> Result<SyncReport, Box<dyn std::error::Error>> {
     let zuko_database = ZUKO_DATABASE.get().expect("ZUKO_DATABASE not initialized!");
-    zuko_database.sync().await.expect("Failed to sync zuko DB");
-}   
\ No newline at end of file
+
+    // the snapshot covers syncs made outside zuko too; without one, compare
+    // against the replica as it is right now
+    let mut before = sync_history::get_snapshot_hashes().await?;
+    if before.is_empty() {
+        before = sync_history::hash_questions().await?;
+    }
+
+    let replicated = zuko_database.sync().await?;
+
+    let after = sync_history::hash_questions().await?;
+    let report = SyncReport {
+        frames_synced: replicated.frames_synced(),
+        frame_no: replicated.frame_no(),
+        diff: SyncDiff::between(&before, &after),
+    };
+    sync_history::record_sync(&report, &after, before.is_empty()).await?;
+
+    Ok(report)
+}
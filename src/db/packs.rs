@@ -0,0 +1,255 @@
+use crate::db::get_zuko_user_db;
+use crate::db::local_questions::to_json_column;
+use crate::types::{InstalledPack, Question, QuestionSummary, Topic};
+use crate::utils::problem_pack::{is_valid_pack_name, ProblemPack};
+use libsql::{de, params, Value};
+
+/// Installed packs. Each one's questions and topics live in their own tables,
+/// see [`question_table`] and [`topic_table`].
+const CREATE_PACK_TABLE: &str = "CREATE TABLE IF NOT EXISTS Pack (
+    name TEXT PRIMARY KEY,
+    version TEXT NOT NULL,
+    author TEXT NOT NULL,
+    license TEXT NOT NULL,
+    description TEXT,
+    question_count INTEGER NOT NULL,
+    installed_at TEXT NOT NULL
+)";
+
+pub async fn initialize_packs() -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    db.execute(CREATE_PACK_TABLE, ()).await?;
+    Ok(())
+}
+
+/// Table holding the questions of pack `name`, in the shape of `QuestionList`.
+///
+/// Names are checked by [`is_valid_pack_name`] before they get here, so they
+/// and the table names are safe to interpolate.
+fn question_table(name: &str) -> String {
+    format!("PackQuestion_{}", name.replace('-', "_"))
+}
+
+fn topic_table(name: &str) -> String {
+    format!("PackTopic_{}", name.replace('-', "_"))
+}
+
+/// Installs `pack`, replacing any installed version of it, and returns the
+/// version that was replaced.
+pub async fn install_pack(pack: &ProblemPack) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let manifest = &pack.manifest;
+    if !is_valid_pack_name(&manifest.name) {
+        return Err(format!("invalid pack name '{}'", manifest.name).into());
+    }
+    let previous = get_installed_packs()
+        .await?
+        .into_iter()
+        .find(|p| p.name == manifest.name)
+        .map(|p| p.version);
+
+    let db = get_zuko_user_db();
+    let questions = question_table(&manifest.name);
+    let topics = topic_table(&manifest.name);
+
+    let tx = db.transaction().await?;
+    tx.execute(&format!("DROP TABLE IF EXISTS {questions}"), ()).await?;
+    tx.execute(&format!("DROP TABLE IF EXISTS {topics}"), ()).await?;
+    tx.execute(
+        &format!(
+            "CREATE TABLE {questions} (
+                title_slug TEXT PRIMARY KEY,
+                title TEXT NOT NULL,
+                content TEXT NOT NULL,
+                difficulty TEXT,
+                topic TEXT,
+                hints TEXT,
+                example_testcase_list TEXT,
+                similar_question_list TEXT,
+                next_challenges TEXT
+            )"
+        ),
+        (),
+    )
+    .await?;
+    tx.execute(
+        &format!("CREATE TABLE {topics} (slug TEXT PRIMARY KEY, name TEXT NOT NULL)"),
+        (),
+    )
+    .await?;
+
+    for question in &pack.questions {
+        tx.execute(
+            &format!(
+                "INSERT INTO {questions} (title_slug, title, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"
+            ),
+            params![
+                question.title_slug.as_str(),
+                question.title.as_str(),
+                question.content.as_str(),
+                question.difficulty.clone(),
+                to_json_column(&question.topic)?,
+                to_json_column(&question.hints)?,
+                to_json_column(&question.example_testcase_list)?,
+                to_json_column(&question.similar_question_list)?,
+                to_json_column(&question.next_challenges)?
+            ],
+        )
+        .await?;
+    }
+    for topic in &pack.topics {
+        tx.execute(
+            &format!("INSERT INTO {topics} (slug, name) VALUES (?1, ?2)"),
+            params![topic.slug.as_str(), topic.name.as_str()],
+        )
+        .await?;
+    }
+
+    tx.execute(
+        "INSERT INTO Pack (name, version, author, license, description, question_count, installed_at)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+         ON CONFLICT(name) DO UPDATE SET
+            version = excluded.version,
+            author = excluded.author,
+            license = excluded.license,
+            description = excluded.description,
+            question_count = excluded.question_count,
+            installed_at = excluded.installed_at",
+        params![
+            manifest.name.as_str(),
+            manifest.version.as_str(),
+            manifest.author.as_str(),
+            manifest.license.as_str(),
+            manifest.description.clone(),
+            pack.questions.len() as i64,
+            chrono::Utc::now().to_rfc3339()
+        ],
+    )
+    .await?;
+    tx.commit().await?;
+
+    Ok(previous)
+}
+
+/// Removes pack `name` and its tables, returning whether it was installed.
+pub async fn remove_pack(name: &str) -> Result<bool, Box<dyn std::error::Error>> {
+    if !is_valid_pack_name(name) {
+        return Ok(false);
+    }
+    let db = get_zuko_user_db();
+
+    let tx = db.transaction().await?;
+    let removed = tx.execute("DELETE FROM Pack WHERE name = ?1", params![name]).await?;
+    tx.execute(&format!("DROP TABLE IF EXISTS {}", question_table(name)), ()).await?;
+    tx.execute(&format!("DROP TABLE IF EXISTS {}", topic_table(name)), ()).await?;
+    tx.commit().await?;
+
+    Ok(removed > 0)
+}
+
+pub async fn get_installed_packs() -> Result<Vec<InstalledPack>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db
+        .query(
+            "SELECT name, version, author, license, description, question_count, installed_at FROM Pack ORDER BY name",
+            (),
+        )
+        .await?;
+
+    let mut packs = Vec::new();
+    while let Some(row) = rows.next().await? {
+        packs.push(de::from_row::<InstalledPack>(&row)?);
+    }
+    Ok(packs)
+}
+
+/// List columns of every installed pack's questions.
+pub async fn get_pack_question_summaries() -> Result<Vec<QuestionSummary>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut questions = Vec::new();
+    let packs = get_installed_packs().await?;
+    for pack in packs {
+        let mut rows = db
+            .query(
+                &format!(
+                    "SELECT title, title_slug, difficulty, topic, 'pack:{}' AS source FROM {}",
+                    pack.name,
+                    question_table(&pack.name)
+                ),
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            questions.push(de::from_row::<QuestionSummary>(&row)?);
+        }
+    }
+    Ok(questions)
+}
+
+/// Full questions of every installed pack.
+pub async fn get_pack_questions() -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    get_pack_questions_where("", Vec::new()).await
+}
+
+/// Loads the pack questions among `title_slugs`; other slugs are skipped.
+pub async fn get_pack_questions_by_slug(
+    title_slugs: &[String],
+) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    if title_slugs.is_empty() {
+        return Ok(Vec::new());
+    }
+    let placeholders = (1..=title_slugs.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let values = title_slugs.iter().map(|s| Value::from(s.as_str())).collect();
+    get_pack_questions_where(&format!(" WHERE title_slug IN ({placeholders})"), values).await
+}
+
+async fn get_pack_questions_where(
+    condition: &str,
+    values: Vec<Value>,
+) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut questions = Vec::new();
+    let packs = get_installed_packs().await?;
+    for pack in packs {
+        let mut rows = db
+            .query(
+                &format!(
+                    "SELECT title, title_slug, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges, 'pack:{}' AS source FROM {}{condition}",
+                    pack.name,
+                    question_table(&pack.name)
+                ),
+                values.clone(),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            questions.push(de::from_row::<Question>(&row)?);
+        }
+    }
+    Ok(questions)
+}
+
+/// Topics declared by installed packs, in pack order.
+pub async fn get_pack_topics() -> Result<Vec<Topic>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut topics = Vec::new();
+    let packs = get_installed_packs().await?;
+    for pack in packs {
+        let mut rows = db
+            .query(
+                &format!("SELECT name, slug FROM {} ORDER BY name", topic_table(&pack.name)),
+                (),
+            )
+            .await?;
+        while let Some(row) = rows.next().await? {
+            topics.push(de::from_row::<Topic>(&row)?);
+        }
+    }
+    Ok(topics)
+}
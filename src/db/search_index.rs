@@ -0,0 +1,164 @@
+use crate::db::get_zuko_user_db;
+use crate::db::zuko_cli::get_questions_of_every_source;
+use crate::types::ContentMatch;
+use crate::utils::parse_html::html_to_plain_text;
+use libsql::{de, params};
+use std::collections::HashMap;
+
+/// Marks the start and end of a matched term inside a snippet.
+pub const SNIPPET_START: char = '\u{2}';
+pub const SNIPPET_END: char = '\u{3}';
+
+/// Full-text index over the synced questions. It lives in the local user database
+/// because the replica is read-only; `title_slug` links rows back to `QuestionList`.
+const CREATE_SEARCH_TABLE: &str = "CREATE VIRTUAL TABLE IF NOT EXISTS QuestionSearch USING fts5(
+    title_slug UNINDEXED,
+    title,
+    content,
+    hints,
+    tokenize = 'porter unicode61'
+)";
+
+/// Creates the index and fills it if it has never been built, so searches don't
+/// have to.
+pub async fn initialize_search_index() -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    db.execute(CREATE_SEARCH_TABLE, ()).await?;
+    if is_search_index_empty().await? {
+        rebuild_search_index().await?;
+    }
+    Ok(())
+}
+
+/// Re-populates the index from every question source; run after every sync,
+/// import or pack change.
+pub async fn rebuild_search_index() -> Result<usize, Box<dyn std::error::Error>> {
+    let questions = get_questions_of_every_source().await?;
+
+    let db = get_zuko_user_db();
+    let tx = db.transaction().await?;
+    tx.execute("DELETE FROM QuestionSearch", ()).await?;
+    for question in &questions {
+        let hints = question
+            .hints
+            .iter()
+            .flatten()
+            .map(|hint| html_to_plain_text(hint))
+            .collect::<Vec<_>>()
+            .join("\n");
+        tx.execute(
+            "INSERT INTO QuestionSearch (title_slug, title, content, hints) VALUES (?1, ?2, ?3, ?4)",
+            params![
+                question.title_slug.as_str(),
+                question.title.as_str(),
+                html_to_plain_text(&question.content),
+                hints
+            ],
+        )
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(questions.len())
+}
+
+/// The plain-text statement of every indexed question, keyed by title slug.
+pub async fn get_search_contents() -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    let mut rows = db.query("SELECT title_slug, content FROM QuestionSearch", ()).await?;
+
+    let mut contents = HashMap::new();
+    while let Some(row) = rows.next().await? {
+        contents.insert(row.get::<String>(0)?, row.get::<String>(1)?);
+    }
+    Ok(contents)
+}
+
+async fn is_search_index_empty() -> Result<bool, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    let mut rows = db.query("SELECT 1 FROM QuestionSearch LIMIT 1", ()).await?;
+    Ok(rows.next().await?.is_none())
+}
+
+/// Ranks questions whose title, statement or hints match `query`, best first.
+///
+/// `query` is plain text: words must all appear (the last one as a prefix, so
+/// results update while typing) and double-quoted sections match as phrases.
+pub async fn search_question_content(
+    query: &str,
+    limit: usize,
+) -> Result<Vec<ContentMatch>, Box<dyn std::error::Error>> {
+    let fts_query = to_fts_query(query);
+    if fts_query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let db = get_zuko_user_db();
+    let sql = format!(
+        "SELECT title_slug, title, snippet(QuestionSearch, -1, '{SNIPPET_START}', '{SNIPPET_END}', '…', 16) AS snippet
+         FROM QuestionSearch
+         WHERE QuestionSearch MATCH ?1
+         ORDER BY bm25(QuestionSearch, 0.0, 10.0, 1.0, 2.0)
+         LIMIT ?2"
+    );
+    let mut rows = db.query(&sql, params![fts_query, limit as i64]).await?;
+
+    let mut matches = Vec::new();
+    while let Some(row) = rows.next().await? {
+        matches.push(de::from_row::<ContentMatch>(&row)?);
+    }
+    Ok(matches)
+}
+
+/// Turns free text into an FTS5 expression, quoting every token so user input
+/// can never be a syntax error.
+fn to_fts_query(query: &str) -> String {
+    let mut terms = Vec::new();
+    for (i, part) in query.split('"').enumerate() {
+        // odd parts were inside double quotes
+        if i % 2 == 1 {
+            if !part.trim().is_empty() {
+                terms.push(format!("\"{}\"", part.trim()));
+            }
+            continue;
+        }
+        terms.extend(
+            part.split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .map(|word| format!("\"{word}\"")),
+        );
+    }
+
+    let ends_with_word = query.chars().last().is_some_and(char::is_alphanumeric);
+    if ends_with_word && let Some(last) = terms.last_mut() {
+        last.push('*');
+    }
+    terms.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quotes_every_word_and_prefixes_the_last() {
+        assert_eq!(to_fts_query("two sum"), r#""two" "sum"*"#);
+        assert_eq!(to_fts_query("two sum "), r#""two" "sum""#);
+        assert_eq!(to_fts_query("a-b c"), r#""a" "b" "c"*"#);
+    }
+
+    #[test]
+    fn keeps_quoted_phrases_together() {
+        assert_eq!(to_fts_query(r#""shortest path" graph"#), r#""shortest path" "graph"*"#);
+        assert_eq!(to_fts_query(r#"graph "shortest path""#), r#""graph" "shortest path""#);
+        // an unterminated quote still counts as a phrase while typing
+        assert_eq!(to_fts_query(r#"graph "shortest pa"#), r#""graph" "shortest pa"*"#);
+    }
+
+    #[test]
+    fn never_passes_syntax_through() {
+        assert_eq!(to_fts_query("NOT sum OR (x)*"), r#""NOT" "sum" "OR" "x""#);
+        assert_eq!(to_fts_query(r#""""#), "");
+        assert_eq!(to_fts_query("  *^ "), "");
+    }
+}
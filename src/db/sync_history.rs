@@ -0,0 +1,237 @@
+use crate::db::{get_zuko_db, get_zuko_user_db};
+use crate::types::{NewQuestion, SyncDiff, SyncLogEntry, SyncReport};
+use libsql::{de, params, Value};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+
+/// One row per sync, newest last.
+const CREATE_SYNC_LOG_TABLE: &str = "CREATE TABLE IF NOT EXISTS SyncLog (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    synced_at TEXT NOT NULL,
+    frame_no INTEGER,
+    frames_synced INTEGER NOT NULL,
+    added INTEGER NOT NULL,
+    modified INTEGER NOT NULL,
+    removed INTEGER NOT NULL
+)";
+
+/// The `QuestionList` as of the last sync. `first_seen_at` is NULL for questions
+/// that were already there when history started being recorded.
+const CREATE_QUESTION_SNAPSHOT_TABLE: &str = "CREATE TABLE IF NOT EXISTS QuestionSnapshot (
+    title_slug TEXT PRIMARY KEY,
+    content_hash TEXT NOT NULL,
+    first_seen_at TEXT
+)";
+
+pub async fn initialize_sync_history() -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    db.execute(CREATE_SYNC_LOG_TABLE, ()).await?;
+    db.execute(CREATE_QUESTION_SNAPSHOT_TABLE, ()).await?;
+    Ok(())
+}
+
+/// Hashes every question in the replica, keyed by title slug.
+pub async fn hash_questions() -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let db = get_zuko_db();
+
+    let mut rows = db
+        .query(
+            "SELECT title_slug, title, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges FROM QuestionList",
+            (),
+        )
+        .await?;
+
+    let mut hashes = HashMap::new();
+    while let Some(row) = rows.next().await? {
+        let mut hasher = Sha256::new();
+        for i in 1..row.column_count() {
+            let value = row.get::<Option<String>>(i)?.unwrap_or_default();
+            // length-prefixed so moving text between columns changes the hash
+            hasher.update(value.len().to_le_bytes());
+            hasher.update(value.as_bytes());
+        }
+        hashes.insert(row.get::<String>(0)?, format!("{:x}", hasher.finalize()));
+    }
+    Ok(hashes)
+}
+
+/// Hashes recorded by the last sync; empty before the first one.
+pub async fn get_snapshot_hashes() -> Result<HashMap<String, String>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db
+        .query("SELECT title_slug, content_hash FROM QuestionSnapshot", ())
+        .await?;
+
+    let mut hashes = HashMap::new();
+    while let Some(row) = rows.next().await? {
+        hashes.insert(row.get::<String>(0)?, row.get::<String>(1)?);
+    }
+    Ok(hashes)
+}
+
+/// Stores the post-sync hashes and logs the sync.
+///
+/// `initial_load` is set when the replica was empty before the sync; the whole
+/// problem set is then recorded without marking anything as new.
+pub async fn record_sync(
+    report: &SyncReport,
+    hashes: &HashMap<String, String>,
+    initial_load: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    let synced_at = chrono::Utc::now().to_rfc3339();
+    let diff = &report.diff;
+    let added: HashSet<&String> = diff.added.iter().collect();
+
+    let tx = db.transaction().await?;
+    for slug in &diff.removed {
+        tx.execute("DELETE FROM QuestionSnapshot WHERE title_slug = ?1", params![slug.as_str()])
+            .await?;
+    }
+    for (slug, hash) in hashes {
+        let first_seen_at = (!initial_load && added.contains(slug)).then(|| synced_at.clone());
+        tx.execute(
+            "INSERT INTO QuestionSnapshot (title_slug, content_hash, first_seen_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(title_slug) DO UPDATE SET content_hash = excluded.content_hash",
+            params![slug.as_str(), hash.as_str(), first_seen_at],
+        )
+        .await?;
+    }
+    tx.execute(
+        "INSERT INTO SyncLog (synced_at, frame_no, frames_synced, added, modified, removed)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+        params![
+            synced_at.as_str(),
+            report.frame_no.map(|f| f as i64),
+            report.frames_synced as i64,
+            diff.added.len() as i64,
+            diff.modified.len() as i64,
+            diff.removed.len() as i64
+        ],
+    )
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn get_last_sync() -> Result<Option<SyncLogEntry>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+
+    let mut rows = db
+        .query(
+            "SELECT synced_at, frame_no, frames_synced, added, modified, removed FROM SyncLog ORDER BY id DESC LIMIT 1",
+            (),
+        )
+        .await?;
+
+    match rows.next().await? {
+        Some(row) => Ok(Some(de::from_row::<SyncLogEntry>(&row)?)),
+        None => Ok(None),
+    }
+}
+
+/// Questions first seen by a sync at or after `since` (RFC 3339, UTC), newest first.
+pub async fn get_questions_added_since(since: &str) -> Result<Vec<NewQuestion>, Box<dyn std::error::Error>> {
+    let user_db = get_zuko_user_db();
+
+    let mut rows = user_db
+        .query(
+            "SELECT title_slug, first_seen_at FROM QuestionSnapshot
+             WHERE first_seen_at >= ?1
+             ORDER BY first_seen_at DESC, title_slug",
+            params![since],
+        )
+        .await?;
+
+    let mut first_seen = Vec::new();
+    while let Some(row) = rows.next().await? {
+        first_seen.push((row.get::<String>(0)?, row.get::<String>(1)?));
+    }
+
+    if first_seen.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // titles live in the replica, which is a separate database
+    let db = get_zuko_db();
+    let placeholders = (1..=first_seen.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let query = format!("SELECT title_slug, title, difficulty FROM QuestionList WHERE title_slug IN ({placeholders})");
+    let values: Vec<Value> = first_seen.iter().map(|(slug, _)| Value::from(slug.as_str())).collect();
+
+    let mut rows = db.query(&query, values).await?;
+    let mut titles = HashMap::new();
+    while let Some(row) = rows.next().await? {
+        titles.insert(row.get::<String>(0)?, (row.get::<String>(1)?, row.get::<Option<String>>(2)?));
+    }
+
+    // questions removed since they were added are left out
+    Ok(first_seen
+        .into_iter()
+        .filter_map(|(title_slug, first_seen_at)| {
+            let (title, difficulty) = titles.remove(&title_slug)?;
+            Some(NewQuestion { title, title_slug, difficulty, first_seen_at })
+        })
+        .collect())
+}
+
+impl SyncDiff {
+    /// Compares the question hashes from before and after a sync.
+    pub fn between(before: &HashMap<String, String>, after: &HashMap<String, String>) -> Self {
+        let mut diff = SyncDiff::default();
+        for (slug, hash) in after {
+            match before.get(slug) {
+                None => diff.added.push(slug.clone()),
+                Some(old) if old != hash => diff.modified.push(slug.clone()),
+                Some(_) => {}
+            }
+        }
+        diff.removed = before
+            .keys()
+            .filter(|slug| !after.contains_key(*slug))
+            .cloned()
+            .collect();
+
+        diff.added.sort();
+        diff.modified.sort();
+        diff.removed.sort();
+        diff
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hashes(entries: &[(&str, &str)]) -> HashMap<String, String> {
+        entries.iter().map(|(slug, hash)| (slug.to_string(), hash.to_string())).collect()
+    }
+
+    #[test]
+    fn sorts_changes_into_added_modified_and_removed() {
+        let before = hashes(&[("two-sum", "a"), ("add-two-numbers", "b"), ("3sum", "c"), ("lru-cache", "d")]);
+        let after = hashes(&[("two-sum", "a"), ("add-two-numbers", "B"), ("zigzag", "e"), ("4sum", "f")]);
+
+        let diff = SyncDiff::between(&before, &after);
+        assert_eq!(diff.added, ["4sum", "zigzag"]);
+        assert_eq!(diff.modified, ["add-two-numbers"]);
+        assert_eq!(diff.removed, ["3sum", "lru-cache"]);
+    }
+
+    #[test]
+    fn finds_nothing_between_equal_snapshots() {
+        let snapshot = hashes(&[("two-sum", "a"), ("3sum", "c")]);
+        let diff = SyncDiff::between(&snapshot, &snapshot);
+        assert!(diff.added.is_empty() && diff.modified.is_empty() && diff.removed.is_empty());
+    }
+
+    #[test]
+    fn everything_is_added_to_an_empty_snapshot() {
+        let diff = SyncDiff::between(&HashMap::new(), &hashes(&[("b", "1"), ("a", "2")]));
+        assert_eq!(diff.added, ["a", "b"]);
+        assert!(diff.modified.is_empty() && diff.removed.is_empty());
+    }
+}
@@ -1,96 +1,134 @@
 use crate::db::get_zuko_db;
-use crate::types::{Question,Topic};
-use libsql::{de, params};
-
-/// Sanitizes a slug string to only allow alphanumeric characters, hyphens, and underscores.
-/// This prevents SQL injection when slugs are interpolated into queries.
-fn sanitize_slug(input: &str) -> String {
-    input
-        .chars()
-        .filter(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
-        .collect()
-}
+use crate::db::local_questions::{
+    get_local_question_summaries, get_local_questions, get_local_questions_by_slug, get_local_topics,
+};
+use crate::db::packs::{get_pack_question_summaries, get_pack_questions, get_pack_questions_by_slug, get_pack_topics};
+use crate::types::{Question, QuestionSummary, Topic};
+use libsql::{de, Value};
+use std::collections::HashSet;
+
+const BASE_QUERY: &str = "SELECT title, title_slug, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges, 'synced' AS source FROM QuestionList";
+
+/// Leaves out the statement and the other large columns, see [`get_questions`].
+const SUMMARY_QUERY: &str = "SELECT title, title_slug, difficulty, topic, 'synced' AS source FROM QuestionList";
+
+/// Loads every synced question. Filters are applied in memory once the sources
+/// are merged, see [`get_questions_of_every_source`], since a synced question
+/// hides an imported one of the same slug whether or not it matches.
+pub async fn get_all_questions() -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    let db = get_zuko_db();
 
-/// Validates that a difficulty string is one of the known safe values (case-insensitive).
-/// Returns the canonical DB representation (e.g. "EASY") or `None` if unrecognised.
-fn validate_difficulty(input: &str) -> Option<&'static str> {
-    match input.to_lowercase().as_str() {
-        "easy" => Some("EASY"),
-        "medium" => Some("MEDIUM"),
-        "hard" => Some("HARD"),
-        _ => None,
+    let mut rows = db.query(BASE_QUERY, ()).await?;
+
+    let mut questions = Vec::new();
+    while let Some(row) = rows.next().await? {
+        questions.push(de::from_row::<Question>(&row)?);
     }
+    Ok(questions)
 }
 
-const BASE_QUERY: &str = "SELECT title, title_slug, content, difficulty, topic, hints, example_testcase_list, similar_question_list, next_challenges FROM QuestionList";
-
-/// JSON existence condition for topic filtering; uses `?1` as the placeholder for the slug.
-const TOPIC_CONDITION: &str =
-    "EXISTS (SELECT 1 FROM json_each(QuestionList.topic) WHERE json_each.value ->> '$.slug' = ?1)";
+/// Loads the list columns of every question, which is all the TUI needs to start.
+/// Imported questions follow the synced ones, then those of installed packs.
+pub async fn get_all_question_summaries() -> Result<Vec<QuestionSummary>, Box<dyn std::error::Error>> {
+    let mut questions = get_summaries_of_every_source().await?;
+    dedupe_by_slug(&mut questions, |q| &q.title_slug);
+    Ok(questions)
+}
 
-pub async fn get_all_questions(
-    topic_slug: Option<String>,
-    difficulty: Option<String>,
-)  -> Result<Vec<Question>, Box<dyn std::error::Error>>  {
+/// Imported and pack questions hidden by a question of the same slug from a
+/// source that takes precedence, e.g. one that a sync added later.
+pub async fn get_shadowed_questions() -> Result<Vec<QuestionSummary>, Box<dyn std::error::Error>> {
+    let mut questions = get_summaries_of_every_source().await?;
+    Ok(dedupe_by_slug(&mut questions, |q| &q.title_slug))
+}
 
+async fn get_summaries_of_every_source() -> Result<Vec<QuestionSummary>, Box<dyn std::error::Error>> {
     let db = get_zuko_db();
 
-    let safe_slug = topic_slug
-        .as_deref()
-        .filter(|s| !s.is_empty())
-        .map(sanitize_slug);
-
-    let safe_difficulty = difficulty
-        .as_deref()
-        .filter(|d| d.to_lowercase() != "all")
-        .and_then(validate_difficulty);
-
-    // Select the appropriate query variant and bind parameters to avoid SQL injection.
-    let mut rows = match (safe_slug.as_deref(), safe_difficulty) {
-        (Some(slug), Some(diff)) => {
-            let q = format!("{BASE_QUERY} WHERE {TOPIC_CONDITION} AND difficulty = ?2");
-            db.query(&q, params![slug, diff]).await?
-        }
-        (Some(slug), None) => {
-            let q = format!("{BASE_QUERY} WHERE {TOPIC_CONDITION}");
-            db.query(&q, params![slug]).await?
-        }
-        (None, Some(diff)) => {
-            db.query(&format!("{BASE_QUERY} WHERE difficulty = ?1"), params![diff]).await?
-        }
-        (None, None) => {
-            db.query(BASE_QUERY, ()).await?
-        }
-    };
+    let mut rows = db.query(SUMMARY_QUERY, ()).await?;
 
     let mut questions = Vec::new();
     while let Some(row) = rows.next().await? {
-        questions.push(de::from_row::<Question>(&row)?);
+        questions.push(de::from_row::<QuestionSummary>(&row)?);
     }
+    questions.extend(get_local_question_summaries().await?);
+    questions.extend(get_pack_question_summaries().await?);
+    Ok(questions)
+}
+
+/// Loads every full question of every source, e.g. for the search index or an export.
+pub async fn get_questions_of_every_source() -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    let mut questions = get_all_questions().await?;
+    questions.extend(get_local_questions().await?);
+    questions.extend(get_pack_questions().await?);
+    dedupe_by_slug(&mut questions, |q| &q.title_slug);
     Ok(questions)
+}
 
+/// Keeps the first question of every slug and returns the ones dropped. Sources
+/// are loaded in [`QuestionSource`](crate::types::QuestionSource) order, so the
+/// synced set wins over imports, and imports over packs.
+fn dedupe_by_slug<T>(questions: &mut Vec<T>, slug: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut seen = HashSet::new();
+    questions
+        .extract_if(.., |q| !seen.insert(slug(q).to_string()))
+        .collect()
 }
 
-#[allow(dead_code)]
-pub async fn get_question(title_slug: String) -> Result<Question, Box<dyn std::error::Error>> {
+/// Title slugs of the synced questions.
+pub async fn get_synced_question_slugs() -> Result<HashSet<String>, Box<dyn std::error::Error>> {
     let db = get_zuko_db();
 
-    let mut stmt = db.prepare("SELECT * FROM QuestionList WHERE title_slug = $1").await.unwrap();
+    let mut rows = db.query("SELECT title_slug FROM QuestionList", ()).await?;
+
+    let mut slugs = HashSet::new();
+    while let Some(row) = rows.next().await? {
+        slugs.insert(row.get::<String>(0)?);
+    }
+    Ok(slugs)
+}
+
+/// Loads the full questions for `title_slugs`, synced, imported or from a pack; slugs without
+/// a question are skipped.
+pub async fn get_questions(title_slugs: &[String]) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    if title_slugs.is_empty() {
+        return Ok(Vec::new());
+    }
 
+    let db = get_zuko_db();
 
-    let row = stmt.query([title_slug]) .await
-        .unwrap()
-        .next()
-        .await
-        .unwrap()
-        .unwrap();
+    let placeholders = (1..=title_slugs.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let query = format!("{BASE_QUERY} WHERE title_slug IN ({placeholders})");
+    let values: Vec<Value> = title_slugs.iter().map(|s| Value::from(s.as_str())).collect();
 
-    let question = de::from_row::<Question>(&row).unwrap();
+    let mut rows = db.query(&query, values).await?;
 
-    Ok(question)
+    let mut questions = Vec::new();
+    while let Some(row) = rows.next().await? {
+        questions.push(de::from_row::<Question>(&row)?);
+    }
+
+    let missing: Vec<String> = title_slugs
+        .iter()
+        .filter(|slug| !questions.iter().any(|q| &q.title_slug == *slug))
+        .cloned()
+        .collect();
+    questions.extend(get_local_questions_by_slug(&missing).await?);
+
+    let missing: Vec<String> = missing
+        .into_iter()
+        .filter(|slug| !questions.iter().any(|q| &q.title_slug == slug))
+        .collect();
+    questions.extend(get_pack_questions_by_slug(&missing).await?);
+    Ok(questions)
 }
 
 
+/// Loads the synced topics followed by any new ones used by imported questions or
+/// declared by installed packs.
 pub async fn get_all_topics() -> Result<Vec<Topic>, Box<dyn std::error::Error>> {
     let db = get_zuko_db();
 
@@ -103,6 +141,13 @@ pub async fn get_all_topics() -> Result<Vec<Topic>, Box<dyn std::error::Error>>
                 let topic: Topic = de::from_row(&row)?;
                 topics.push(topic);
             }
+            let mut extra = get_local_topics().await?;
+            extra.extend(get_pack_topics().await?);
+            for topic in extra {
+                if !topics.iter().any(|t| t.slug == topic.slug) {
+                    topics.push(topic);
+                }
+            }
             Ok(topics)
         }
         Err(e) => Err(format!("Failed to fetch topics: {}", e).into()),
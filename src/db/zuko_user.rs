@@ -1,45 +1,109 @@
-// use libsql::{Builder, Connection};
-// use crate::db::{ZUKO_USER_DB};
+use crate::db::get_zuko_user_db;
+use crate::db::local_questions::initialize_local_questions;
+use crate::db::packs::initialize_packs;
+use crate::db::search_index::initialize_search_index;
+use crate::db::sync_history::initialize_sync_history;
+use crate::types::{Progress, ProgressStatus};
+use libsql::{de, params};
+use std::collections::HashMap;
 
-// pub async fn connect_to_zuko_user_db(db_path: String) -> Result<Connection, String> {
+/// Progress lives in a local database because the zuko replica is read-only.
+const CREATE_PROGRESS_TABLE: &str = "CREATE TABLE IF NOT EXISTS Progress (
+    title_slug TEXT PRIMARY KEY,
+    status TEXT NOT NULL DEFAULT 'TODO',
+    bookmarked INTEGER NOT NULL DEFAULT 0,
+    last_solved_at TEXT
+)";
 
-//     let zuko_user_db = Builder::new_local(
-//             db_path,
-//         )
-//         .build()
-//         .await
-//         .map_err(|e| format!("Failed to build connection to zuko_user.db: {}", e))?;
+/// Creates the user tables if they don't exist yet.
+pub async fn initialize_zuko_user_db() -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
+    db.execute(CREATE_PROGRESS_TABLE, ()).await?;
+    initialize_local_questions().await?;
+    initialize_packs().await?;
+    initialize_search_index().await?;
+    initialize_sync_history().await?;
+    Ok(())
+}
 
-//         let zuko_user_db_connection = zuko_user_db.connect().map_err(|e| format!("Failed to connect to zuko_user.db: {}", e))?;
+/// Returns the progress of every question the user has interacted with, keyed by title slug.
+pub async fn get_all_progress() -> Result<HashMap<String, Progress>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
 
-//         // get or initialize the ZUKO_USER_DB
-//         ZUKO_USER_DB
-//             .set(zuko_user_db_connection.clone())
-//             .expect("ZUKO_USER_DB already initialized!");
+    let mut rows = db
+        .query("SELECT title_slug, status, bookmarked, last_solved_at FROM Progress", ())
+        .await?;
 
-//         Ok(zuko_user_db_connection)
-// }
+    let mut progress = HashMap::new();
+    while let Some(row) = rows.next().await? {
+        let entry = de::from_row::<Progress>(&row)?;
+        progress.insert(entry.title_slug.clone(), entry);
+    }
+    Ok(progress)
+}
 
+pub async fn set_bookmarked(title_slug: &str, bookmarked: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
 
-// pub async fn initialize_zuko_user_db(db_path: String) {
+    db.execute(
+        "INSERT INTO Progress (title_slug, bookmarked) VALUES (?1, ?2)
+         ON CONFLICT(title_slug) DO UPDATE SET bookmarked = excluded.bookmarked",
+        params![title_slug, bookmarked],
+    )
+    .await?;
+    Ok(())
+}
 
-//     let zuko_user_db_connection = connect_to_zuko_user_db(db_path).await.expect("Failed to connect to zuko_user.db");
+/// Updates the status of a question; marking it solved also stamps `last_solved_at`.
+pub async fn set_status(
+    title_slug: &str,
+    status: ProgressStatus,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
 
-//     // Create the user table if it doesn't exist
-// }
+    let solved_at = (status == ProgressStatus::Solved).then(|| chrono::Utc::now().to_rfc3339());
 
+    db.execute(
+        "INSERT INTO Progress (title_slug, status, last_solved_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(title_slug) DO UPDATE SET
+            status = excluded.status,
+            last_solved_at = COALESCE(excluded.last_solved_at, Progress.last_solved_at)",
+        params![title_slug, status_str(status), solved_at.clone()],
+    )
+    .await?;
+    Ok(solved_at)
+}
 
+/// Writes progress read back from an export, e.g. by `zuko import`. A missing
+/// `last_solved_at` keeps the one already recorded.
+pub async fn restore_progress(entries: &[Progress]) -> Result<(), Box<dyn std::error::Error>> {
+    let db = get_zuko_user_db();
 
+    let tx = db.transaction().await?;
+    for entry in entries {
+        tx.execute(
+            "INSERT INTO Progress (title_slug, status, bookmarked, last_solved_at) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(title_slug) DO UPDATE SET
+                status = excluded.status,
+                bookmarked = excluded.bookmarked,
+                last_solved_at = COALESCE(excluded.last_solved_at, Progress.last_solved_at)",
+            params![
+                entry.title_slug.as_str(),
+                status_str(entry.status),
+                entry.bookmarked,
+                entry.last_solved_at.clone()
+            ],
+        )
+        .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
 
-
-
-// "CREATE TABLE IF NOT EXISTS users (
-//             id INTEGER PRIMARY KEY AUTOINCREMENT,
-//             username TEXT NOT NULL UNIQUE,
-//             project_name TEXT NOT NULL,
-//             editor_cmd TEXT NOT NULL,
-//             preferred_language_list TEXT NOT NULL,
-//             difficulty TEXT NOT NULL,
-//             init_git BOOLEAN NOT NULL DEFAULT false,
-//             track_progress BOOLEAN NOT NULL DEFAULT false
-//         )"
\ No newline at end of file
+fn status_str(status: ProgressStatus) -> &'static str {
+    match status {
+        ProgressStatus::Todo => "TODO",
+        ProgressStatus::Attempted => "ATTEMPTED",
+        ProgressStatus::Solved => "SOLVED",
+    }
+}
@@ -6,12 +6,20 @@ mod utils;
 mod ui;
 
 use clap::{Parser, Subcommand};
-use db::{ZUKO_DB, ZUKO_DATABASE};
+use db::{ZUKO_DB, ZUKO_DATABASE, ZUKO_USER_DB};
 use libsql::Builder;
 use config::db::{TURSO_DB_URL, TURSO_DB_TOKEN};
+use std::collections::HashMap;
 use std::fs::create_dir_all;
+use std::path::PathBuf;
 
+use crate::commands::show::ShowFormat;
+use crate::config::app_config::{find_project_file, LayeredConfig};
+use crate::config::paths::{move_legacy_home, ZukoPaths};
 use crate::config::zuko_context::ZukoContext;
+use crate::types::{SourceFilter, TopicMatch};
+use crate::utils::problem_export::ExportFormat;
+use crate::utils::problem_import::ImportFormat;
 
 #[derive(Parser)]
 #[command(name = "zuko")]
@@ -22,37 +30,250 @@ use crate::config::zuko_context::ZukoContext;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Override a setting for this run, e.g. --config difficulty=easy
+    #[arg(long = "config", short = 'c', global = true, value_name = "KEY=VALUE")]
+    config_overrides: Vec<String>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    #[command(flatten)]
+    Database(DatabaseCommands),
+
+    /// Read and change settings without re-running init
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+}
+
+/// Commands that open the databases.
+#[derive(Subcommand)]
+enum DatabaseCommands {
     /// Initialize zuko in the current directory
     Init,
 
     /// List questions optionally filtered by topic, difficulty, or solved status
     List {
-        #[arg(long)]
-        topic_slug: Option<String>,
+        /// Topic slugs to filter by, repeated or comma separated
+        #[arg(long = "topic-slug", value_delimiter = ',')]
+        topic_slugs: Vec<String>,
+
+        /// Require every topic instead of any of them
+        #[arg(long, default_value_t = false)]
+        all_topics: bool,
 
         #[arg(long)]
         difficulty: Option<String>,
 
         #[arg(long, default_value_t = false)]
         solved: bool,
+
+        /// Only questions from one source: synced, imported or pack:<name>
+        #[arg(long)]
+        source: Option<SourceFilter>,
+    },
+
+    /// Full-text search over question titles, statements and hints
+    Search {
+        /// Words to look for; wrap phrases in double quotes
+        query: String,
+
+        #[arg(long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Print a question with its hints, example test cases and similar questions
+    Show {
+        title_slug: String,
+
+        /// One of text, md, json or html
+        #[arg(long, default_value = "text")]
+        format: ShowFormat,
     },
 
     /// Sync with the remote zuko database
     Sync,
+
+    /// Import questions from a CSV, JSON or Markdown file into the local problem set
+    Import {
+        /// File to import; the format is taken from its extension unless --format is given
+        file: PathBuf,
+
+        /// One of csv, json or md
+        #[arg(long)]
+        format: Option<ImportFormat>,
+    },
+
+    /// Export questions and progress as JSON, CSV or a Markdown table
+    Export {
+        /// Topic slugs to filter by, repeated or comma separated
+        #[arg(long = "topic-slug", value_delimiter = ',')]
+        topic_slugs: Vec<String>,
+
+        /// Require every topic instead of any of them
+        #[arg(long, default_value_t = false)]
+        all_topics: bool,
+
+        #[arg(long)]
+        difficulty: Option<String>,
+
+        #[arg(long, default_value_t = false)]
+        solved: bool,
+
+        /// Only questions from one source: synced, imported or pack:<name>
+        #[arg(long)]
+        source: Option<SourceFilter>,
+
+        /// One of json, csv or md
+        #[arg(long, default_value = "json")]
+        format: ExportFormat,
+
+        /// File to write instead of stdout
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+
+    /// List questions added by syncs since a date
+    Whatsnew {
+        /// Date to look back to, as YYYY-MM-DD (defaults to a week ago)
+        #[arg(long)]
+        since: Option<String>,
+    },
+
+    /// Install and manage community problem packs
+    Pack {
+        #[command(subcommand)]
+        command: PackCommands,
+    },
+}
+
+#[derive(Subcommand)]
+enum PackCommands {
+    /// Install a pack from a directory or .tar.gz bundle, upgrading any installed version
+    Install {
+        path: PathBuf,
+    },
+
+    /// List installed packs
+    List,
+
+    /// Remove an installed pack and its questions
+    Remove {
+        name: String,
+    },
+
+    /// Check a pack without installing it
+    Validate {
+        path: PathBuf,
+    },
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Print the value of a setting
+    Get {
+        key: String,
+    },
+
+    /// Change a setting; lists like preferred_language_list are comma separated
+    Set {
+        key: String,
+        value: String,
+
+        /// Change the project's .zuko.toml instead of the global config
+        #[arg(long, default_value_t = false)]
+        project: bool,
+    },
+
+    /// Put a setting back to its default, or remove it from the project config
+    Unset {
+        key: String,
+
+        /// Change the project's .zuko.toml instead of the global config
+        #[arg(long, default_value_t = false)]
+        project: bool,
+    },
+
+    /// Print every setting in effect
+    List {
+        /// Show which file, variable or flag each value comes from
+        #[arg(long, default_value_t = false)]
+        origin: bool,
+    },
+
+    /// Open the config in $EDITOR, checking it when the editor exits
+    Edit {
+        /// Edit the project's .zuko.toml instead of the global config
+        #[arg(long, default_value_t = false)]
+        project: bool,
+    },
+
+    /// Print where the config is kept
+    Path {
+        /// Print where the project's .zuko.toml is
+        #[arg(long, default_value_t = false)]
+        project: bool,
+    },
 }
 
 #[tokio::main]
 async fn main() {
     let cli = Cli::parse();
 
-    let mut context = ZukoContext::load_or_default();
+    // once, for configs and databases from before the XDG directories
+    match move_legacy_home(&ZukoPaths::resolve()) {
+        Result::Ok(Some(legacy)) => eprintln!(
+            "Moved zuko's config and databases out of {}, see `zuko config list` for where they are now",
+            legacy.display()
+        ),
+        Result::Ok(None) => {}
+        Result::Err(e) => eprintln!("Failed to move zuko's files out of ~/.zuko: {}", e),
+    }
 
-    // Ensure the db/ subdirectory inside .zuko exists before opening the database
-    let db_dir = context.cli_dir.join("db");
+    let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    let config = match LayeredConfig::load(&current_dir, &cli.config_overrides) {
+        Result::Ok(config) => config,
+        Result::Err(e) => {
+            eprintln!("Failed to load the config: {}", e);
+            // a broken config can still be found and fixed
+            let fixing = matches!(
+                &cli.command,
+                Commands::Config { command: ConfigCommands::Edit { .. } | ConfigCommands::Path { .. } }
+            );
+            if !fixing {
+                return;
+            }
+            LayeredConfig {
+                context: ZukoContext::load_or_default(),
+                origins: HashMap::new(),
+                project_file: find_project_file(&current_dir),
+            }
+        }
+    };
+
+    let command = match &cli.command {
+        Commands::Database(command) => command,
+        // the config is all these need, so they work without the databases
+        Commands::Config { command } => {
+            match command {
+                ConfigCommands::Get { key } => commands::config::get(&config, key),
+                ConfigCommands::Set { key, value, project } => {
+                    commands::config::set(&config, key, value, *project)
+                }
+                ConfigCommands::Unset { key, project } => commands::config::unset(&config, key, *project),
+                ConfigCommands::List { origin } => commands::config::list(&config, *origin),
+                ConfigCommands::Edit { project } => commands::config::edit(&config, *project),
+                ConfigCommands::Path { project } => commands::config::path(&config, *project),
+            }
+            return;
+        }
+    };
+    let context = &config.context;
+
+    // Ensure the db/ subdirectory of the data directory exists before opening the database
+    let db_dir = context.paths.db_dir();
     if !db_dir.exists() {
         create_dir_all(&db_dir)
             .unwrap_or_else(|e| panic!("Failed to create directory {}: {}", db_dir.display(), e));
@@ -60,7 +281,7 @@ async fn main() {
 
     // initialize zuko db (creates local replica if missing, syncs if it already exists)
     let zuko_db = Builder::new_remote_replica(
-        format!("{}", context.cli_dir.join("db/zuko.db").display()).to_string(),
+        format!("{}", db_dir.join("zuko.db").display()),
         TURSO_DB_URL.to_string(), //remote DB connection string
         TURSO_DB_TOKEN.to_string(), //remote DB encryption string
     )
@@ -78,22 +299,98 @@ async fn main() {
         .set(zuko_db_connection)
         .expect("ZUKO_DB already initialized!");
 
-    match &cli.command {
-        Commands::Init => {
-            // Handle the init command
-            commands::init::execute(&mut context).await;
+    // initialize the local user db that holds progress and bookmarks
+    let zuko_user_db = Builder::new_local(db_dir.join("zuko_user.db"))
+        .build()
+        .await
+        .expect("Failed to build connection to zuko_user.db");
+
+    ZUKO_USER_DB
+        .set(zuko_user_db.connect().expect("Failed to connect to zuko_user.db"))
+        .expect("ZUKO_USER_DB already initialized!");
+
+    db::zuko_user::initialize_zuko_user_db()
+        .await
+        .expect("Failed to initialize zuko_user.db");
+
+    match command {
+        DatabaseCommands::Init => {
+            // Handle the init command; it saves the global config, so project
+            // settings, variables and flags must not end up in it
+            let Some(mut global) = commands::config::load_global(&config) else {
+                return;
+            };
+            commands::init::execute(&mut global).await;
         }
-        Commands::List {
-            topic_slug,
+        DatabaseCommands::List {
+            topic_slugs,
+            all_topics,
             difficulty,
             solved,
+            source,
         } => {
             // Handle the list command
-            commands::list::execute(&context, topic_slug.clone(), difficulty.clone(), *solved).await;
+            let topic_match = if *all_topics { TopicMatch::All } else { TopicMatch::Any };
+            commands::list::execute(
+                context,
+                topic_slugs.clone(),
+                topic_match,
+                difficulty.clone(),
+                *solved,
+                source.clone(),
+            )
+            .await;
+        }
+        DatabaseCommands::Search { query, limit } => {
+            // Handle the search command
+            commands::search::execute(query.clone(), *limit).await;
         }
-        Commands::Sync => {
+        DatabaseCommands::Show { title_slug, format } => {
+            // Handle the show command
+            commands::show::execute(title_slug.clone(), *format).await;
+        }
+        DatabaseCommands::Sync => {
             // Handle the sync command
             commands::sync::execute().await;
         }
+        DatabaseCommands::Import { file, format } => {
+            // Handle the import command
+            commands::import::execute(file.clone(), *format).await;
+        }
+        DatabaseCommands::Export {
+            topic_slugs,
+            all_topics,
+            difficulty,
+            solved,
+            source,
+            format,
+            output,
+        } => {
+            // Handle the export command
+            let topic_match = if *all_topics { TopicMatch::All } else { TopicMatch::Any };
+            commands::export::execute(
+                topic_slugs.clone(),
+                topic_match,
+                difficulty.clone(),
+                *solved,
+                source.clone(),
+                *format,
+                output.clone(),
+            )
+            .await;
+        }
+        DatabaseCommands::Whatsnew { since } => {
+            // Handle the whatsnew command
+            commands::whatsnew::execute(since.clone()).await;
+        }
+        DatabaseCommands::Pack { command } => {
+            // Handle the pack commands
+            match command {
+                PackCommands::Install { path } => commands::pack::install(path).await,
+                PackCommands::List => commands::pack::list().await,
+                PackCommands::Remove { name } => commands::pack::remove(name).await,
+                PackCommands::Validate { path } => commands::pack::validate(path).await,
+            }
+        }
     }
 }
@@ -1,6 +1,15 @@
 use clap::ValueEnum;
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap};
 use std::fmt;
+use std::sync::{Arc, OnceLock};
+use std::time::Instant;
+use chrono::{DateTime, Local};
+use crate::utils::fuzzy_matcher::SearchItem;
+use crate::utils::parse_html::parse_statement;
+use crate::utils::question_details::QuestionDetails;
+use crate::utils::question_index::QuestionIndex;
+use crate::utils::search_worker::SearchWorker;
 use crate::utils::serde_json_string;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,23 +33,145 @@ pub struct Question {
     pub content: String,
     pub difficulty: Option<String>,
 
-    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    #[serde(default, with = "serde_json_string")]
     pub topic: Option<Vec<Topic>>,
     
-    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    #[serde(default, with = "serde_json_string")]
     pub hints: Option<Vec<String>>,
 
-    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    #[serde(default, with = "serde_json_string")]
     pub example_testcase_list: Option<Vec<String>>,
 
-    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    #[serde(default, with = "serde_json_string")]
     pub similar_question_list: Option<Vec<SimilarQuestion>>,
     
-    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    #[serde(default, with = "serde_json_string")]
     pub next_challenges: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub source: QuestionSource,
+
+    /// What is read out of `content`, parsed on first use.
+    #[serde(skip)]
+    pub parsed: OnceLock<ParsedStatement>,
+}
+
+impl Question {
+    /// The worked examples of the statement.
+    pub fn examples(&self) -> &[Example] {
+        &self.parsed().examples
+    }
+
+    /// The limits from the statement's constraints.
+    pub fn limits(&self) -> &[Limit] {
+        &self.parsed().limits
+    }
+
+    fn parsed(&self) -> &ParsedStatement {
+        self.parsed.get_or_init(|| parse_statement(&self.content))
+    }
+}
+
+/// The examples and limits of a statement, read in one pass over its HTML.
+#[derive(Debug, Default)]
+pub struct ParsedStatement {
+    pub examples: Vec<Example>,
+    pub limits: Vec<Limit>,
+}
+
+/// A worked example from a question's statement.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Example {
+    pub input: String,
+    pub output: String,
+    pub explanation: Option<String>,
+}
+
+/// A variable's range from a question's constraints, e.g. `1 <= nums.length <= 10^4`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Limit {
+    pub variable: String,
+    pub lower: Option<Bound>,
+    pub upper: Option<Bound>,
+}
+
+/// One end of a [`Limit`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Bound {
+    /// As written, e.g. `2 * 10^5` or `nums.length`.
+    pub text: String,
+    /// `None` when the bound is another variable.
+    pub value: Option<i64>,
+    /// Whether the variable can equal the bound, i.e. `<=` rather than `<`.
+    pub inclusive: bool,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, ValueEnum)]
+/// The columns shown in the question list. The statement, hints and examples are
+/// loaded separately as a [`Question`] when the question is previewed.
+#[derive(Debug, Clone, Deserialize)]
+pub struct QuestionSummary {
+    pub title: String,
+    pub title_slug: String,
+    pub difficulty: Option<String>,
+
+    #[serde(deserialize_with = "serde_json_string::deserialize")]
+    pub topic: Option<Vec<Topic>>,
+
+    #[serde(default)]
+    pub source: QuestionSource,
+}
+
+/// Where a question comes from, written as `synced`, `imported` or `pack:<name>`.
+///
+/// When the same slug exists in several sources the earlier variant wins, see
+/// `db::zuko_cli::dedupe_by_slug`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub enum QuestionSource {
+    /// The replicated `QuestionList`.
+    #[default]
+    Synced,
+    /// Added with `zuko import`.
+    Imported,
+    /// From the installed community pack of that name.
+    Pack(String),
+}
+
+/// The source filter of the question list, like [`DifficultyFilter`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum SourceFilter {
+    #[default]
+    All,
+    Specific(QuestionSource),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum ProgressStatus {
+    #[default]
+    Todo,
+    Attempted,
+    Solved,
+}
+
+/// Per-question progress stored in the local user database.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Progress {
+    pub title_slug: String,
+    pub status: ProgressStatus,
+    pub bookmarked: bool,
+    pub last_solved_at: Option<String>,
+}
+
+/// A full-text search hit; `snippet` marks matched terms with
+/// `search_index::SNIPPET_START` / `SNIPPET_END`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContentMatch {
+    pub title_slug: String,
+    pub title: String,
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ValueEnum)]
 pub enum Difficulty {
     Easy,
     Medium,
@@ -57,15 +188,145 @@ pub enum CurrentScreen {
     QuestionList,
     TopicList,
     DifficultyFilter,
+    SourceFilter,
+}
+
+/// How multiple selected topics combine when filtering questions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TopicMatch {
+    /// Questions tagged with at least one of the topics.
+    #[default]
+    Any,
+    /// Questions tagged with every topic.
+    All,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TopicSelection {
+    pub topics: Vec<Topic>,
+    pub mode: TopicMatch,
+}
+
+/// What the search box in the question list matches against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Fuzzy titles plus the `t:`/`d:`/`src:`/`is:` query syntax.
+    Fuzzy,
+    /// Full-text search over statements and hints.
+    Content,
+}
+
+/// Order of the question list, cycled from the TUI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortOrder {
+    /// Search ranking (or database order when there is no query).
+    Relevance,
+    Title,
+    Difficulty,
+    RecentlySolved,
+    /// Solved questions that were solved longest ago come first.
+    DueForReview,
+    Random,
+}
+
+/// What one call to `db::sync_zuko_db` replicated.
+#[derive(Debug, Clone)]
+pub struct SyncReport {
+    /// Number of 4kB frames pulled from the remote database.
+    pub frames_synced: usize,
+    /// Replication position of the local replica after the sync.
+    pub frame_no: Option<u64>,
+    pub diff: SyncDiff,
+}
+
+/// Title slugs of the questions a sync changed, each list sorted.
+#[derive(Debug, Clone, Default)]
+pub struct SyncDiff {
+    pub added: Vec<String>,
+    pub modified: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+/// A row of the local sync log.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SyncLogEntry {
+    /// RFC 3339, UTC.
+    pub synced_at: String,
+    pub frame_no: Option<i64>,
+    pub frames_synced: i64,
+    pub added: i64,
+    pub modified: i64,
+    pub removed: i64,
+}
+
+/// A row of the local pack registry, see `zuko pack list`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstalledPack {
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    pub license: String,
+    pub description: Option<String>,
+    pub question_count: i64,
+    /// RFC 3339, UTC.
+    pub installed_at: String,
+}
+
+/// A question that appeared in a sync, see `zuko whatsnew`.
+#[derive(Debug, Clone)]
+pub struct NewQuestion {
+    pub title: String,
+    pub title_slug: String,
+    pub difficulty: Option<String>,
+    /// RFC 3339, UTC.
+    pub first_seen_at: String,
+}
+
+/// Progress of a sync started from the TUI.
+#[derive(Debug, Clone, Default)]
+pub enum SyncStatus {
+    #[default]
+    Idle,
+    Running {
+        /// The step in progress, e.g. "Replicating".
+        stage: &'static str,
+        started_at: Instant,
+    },
+    Finished(SyncReport),
+    Failed(String),
 }
 
 pub struct AppState {
-    pub all_questions: Vec<Question>,
+    pub all_questions: Vec<QuestionSummary>,
+    /// Full questions fetched for the preview, keyed by title slug.
+    pub details: QuestionDetails,
+    /// Background matcher over the searchable projection of `all_questions`.
+    pub search: SearchWorker,
+    /// Topic, difficulty and source lookup over `all_questions`, built when they are loaded.
+    pub question_index: QuestionIndex,
+    /// Sorted positions in `all_questions` passing the topic, difficulty and source filters.
+    pub filter_scope: Arc<Vec<usize>>,
+    /// Shared with the search worker, which re-scores from scratch whenever this
+    /// is replaced; changes go through `Arc::make_mut`.
+    pub progress: Arc<HashMap<String, Progress>>,
     pub all_topics: Vec<Topic>,
     pub filtered_topic_indices: Vec<usize>,
     pub selected_topic_index: usize,
-    pub selected_topic: Option<Topic>,
+    /// Topics applied to the question list.
+    pub selected_topics: TopicSelection,
+    /// Topics being toggled in the open topic popup, applied on Enter.
+    pub topic_draft: TopicSelection,
     pub filtered_question_indices: Vec<usize>,
+    /// Matched title char positions, aligned with `filtered_question_indices`.
+    pub title_highlights: Vec<Vec<u32>>,
+    pub search_mode: SearchMode,
+    /// Statement snippets in content mode, aligned with `filtered_question_indices`.
+    pub content_snippets: Vec<String>,
+    /// The query of the full-text search in flight, if any.
+    pub pending_content_query: Option<String>,
+    pub sort_order: SortOrder,
+    /// Seed for `SortOrder::Random`, so the shuffle is stable while typing.
+    pub sort_seed: u64,
     pub query: String,
     pub topic_query: String,
     pub selected_index: usize,
@@ -74,31 +335,186 @@ pub struct AppState {
     pub difficulties: Vec<DifficultyFilter>,
     pub selected_difficulty: DifficultyFilter,
     pub selected_difficulty_index: usize,
+    /// "All" followed by every source present in `all_questions`.
+    pub sources: Vec<SourceFilter>,
+    pub selected_source: SourceFilter,
+    pub selected_source_index: usize,
+    pub sync_status: SyncStatus,
+    /// When the replica was last synced, from the sync log.
+    pub last_synced_at: Option<DateTime<Local>>,
+    /// The last failed action, shown in the footer until the next key press.
+    pub error_message: Option<String>,
+    /// Set when the questions were replaced under the current results, so the
+    /// UI re-runs the search.
+    pub results_stale: bool,
 }
 
 impl AppState {
-    pub fn new(questions: Vec<Question>, topics: Vec<Topic>) -> Self {
+    pub fn new(
+        questions: Vec<QuestionSummary>,
+        topics: Vec<Topic>,
+        progress: HashMap<String, Progress>,
+    ) -> Self {
         AppState {
+            sources: available_sources(&questions),
+            search: SearchWorker::new(build_search_items(&questions)),
+            question_index: QuestionIndex::new(&questions),
+            filter_scope: Arc::new((0..questions.len()).collect()),
             all_questions: questions,
+            details: QuestionDetails::default(),
+            progress: Arc::new(progress),
             filtered_question_indices: Vec::new(),
-            all_topics:topics,
+            title_highlights: Vec::new(),
+            search_mode: SearchMode::Fuzzy,
+            content_snippets: Vec::new(),
+            pending_content_query: None,
+            sort_order: SortOrder::Relevance,
+            sort_seed: 0,
+            all_topics: with_all_topics_entry(topics),
             filtered_topic_indices:Vec::new(),
             difficulties: DifficultyFilter::all_difficulties(),
             selected_difficulty: DifficultyFilter::default(),
             selected_difficulty_index: 0,
+            selected_source: SourceFilter::All,
+            selected_source_index: 0,
             query: String::new(),
             topic_query: String::new(),
             selected_index: 0,
             selected_topic_index: 0,
-            selected_topic: None,
+            selected_topics: TopicSelection::default(),
+            topic_draft: TopicSelection::default(),
             scroll: 0,
             current_screen: CurrentScreen::QuestionList,
+            sync_status: SyncStatus::Idle,
+            last_synced_at: None,
+            error_message: None,
+            results_stale: false,
         }
     }
+
+    /// Swaps in freshly loaded questions, e.g. after a sync, keeping the filters and query.
+    pub fn set_questions(&mut self, questions: Vec<QuestionSummary>) {
+        self.search.set_items(build_search_items(&questions));
+        self.question_index = QuestionIndex::new(&questions);
+        self.sources = available_sources(&questions);
+        // a removed pack can't stay selected
+        match self.sources.iter().position(|s| *s == self.selected_source) {
+            Some(index) => self.selected_source_index = index,
+            None => {
+                self.selected_source = SourceFilter::All;
+                self.selected_source_index = 0;
+            }
+        }
+        self.all_questions = questions;
+        // statements may have changed as well
+        self.details = QuestionDetails::default();
+        self.refresh_filter_scope();
+
+        // the old positions don't point at the same questions anymore
+        self.filtered_question_indices.clear();
+        self.title_highlights.clear();
+        self.content_snippets.clear();
+        self.selected_index = 0;
+        self.results_stale = true;
+    }
+
+    /// Swaps in freshly loaded topics, keeping the "All Topics" entry first.
+    pub fn set_topics(&mut self, topics: Vec<Topic>) {
+        self.all_topics = with_all_topics_entry(topics);
+        self.topic_query.clear();
+        self.filtered_topic_indices = (0..self.all_topics.len()).collect();
+        self.selected_topic_index = 0;
+    }
+
+    /// Lets free-text terms match the statements in `contents`, keyed by title
+    /// slug, and re-runs the search with them.
+    pub fn set_search_contents(&mut self, contents: &HashMap<String, String>) {
+        let items = self
+            .all_questions
+            .iter()
+            .map(|question| {
+                let mut item = SearchItem::from_question(question);
+                if let Some(content) = contents.get(&question.title_slug) {
+                    item.content = content.to_lowercase();
+                }
+                item
+            })
+            .collect();
+        self.search.set_items(items);
+        if self.search_mode == SearchMode::Fuzzy {
+            self.search.submit(&self.query, &self.filter_scope, &self.progress);
+        }
+    }
+
+    /// Whether a fuzzy or full-text search is still running.
+    pub fn is_searching(&self) -> bool {
+        self.search.is_searching() || self.pending_content_query.is_some()
+    }
+
+    /// Recomputes `filter_scope` from the selected topics, difficulty and source.
+    pub fn refresh_filter_scope(&mut self) {
+        self.filter_scope = Arc::new(self.question_index.filter(
+            &self.selected_topics,
+            self.selected_difficulty,
+            &self.selected_source,
+        ));
+    }
+}
+
+fn build_search_items(questions: &[QuestionSummary]) -> Vec<SearchItem> {
+    questions.iter().map(SearchItem::from_question).collect()
+}
+
+/// The entries of the source popup: "All", then the sources in precedence order.
+fn available_sources(questions: &[QuestionSummary]) -> Vec<SourceFilter> {
+    let sources: BTreeSet<&QuestionSource> = questions.iter().map(|q| &q.source).collect();
+    std::iter::once(SourceFilter::All)
+        .chain(sources.into_iter().cloned().map(SourceFilter::Specific))
+        .collect()
+}
+
+/// Prepends the entry that clears the topic filter in the topic popup.
+fn with_all_topics_entry(mut topics: Vec<Topic>) -> Vec<Topic> {
+    topics.insert(
+        0,
+        Topic {
+            name: "All Topics".to_string(),
+            slug: "".to_string(),
+        },
+    );
+    topics
+}
+
+impl SyncStatus {
+    pub fn is_running(&self) -> bool {
+        matches!(self, SyncStatus::Running { .. })
+    }
 }
 
 // ---- implementations ----
 
+impl From<&Question> for QuestionSummary {
+    fn from(question: &Question) -> Self {
+        QuestionSummary {
+            title: question.title.clone(),
+            title_slug: question.title_slug.clone(),
+            difficulty: question.difficulty.clone(),
+            topic: question.topic.clone(),
+            source: question.source.clone(),
+        }
+    }
+}
+
+
+impl Difficulty {
+    pub fn to_str(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Medium => "Medium",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
 
 impl fmt::Display for Difficulty {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -120,15 +536,89 @@ impl fmt::Display for DifficultyFilter {
     }
 }
 
+impl SortOrder {
+    pub fn next(self) -> Self {
+        match self {
+            SortOrder::Relevance => SortOrder::Title,
+            SortOrder::Title => SortOrder::Difficulty,
+            SortOrder::Difficulty => SortOrder::RecentlySolved,
+            SortOrder::RecentlySolved => SortOrder::DueForReview,
+            SortOrder::DueForReview => SortOrder::Random,
+            SortOrder::Random => SortOrder::Relevance,
+        }
+    }
+}
+
+impl fmt::Display for SortOrder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            SortOrder::Relevance => "Relevance",
+            SortOrder::Title => "Title",
+            SortOrder::Difficulty => "Difficulty",
+            SortOrder::RecentlySolved => "Recently Solved",
+            SortOrder::DueForReview => "Due for Review",
+            SortOrder::Random => "Random",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl TopicMatch {
+    pub fn toggle(self) -> Self {
+        match self {
+            TopicMatch::Any => TopicMatch::All,
+            TopicMatch::All => TopicMatch::Any,
+        }
+    }
+}
+
+impl fmt::Display for TopicMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TopicMatch::Any => write!(f, "Any of"),
+            TopicMatch::All => write!(f, "All of"),
+        }
+    }
+}
+
+impl TopicSelection {
+    /// The topics named by `slugs`, e.g. from `--topic-slug`, looked up in `topics`.
+    /// Slugs no topic has are kept, with the slug as the name.
+    pub fn from_slugs(slugs: &[String], mode: TopicMatch, topics: &[Topic]) -> Self {
+        TopicSelection {
+            topics: slugs
+                .iter()
+                .filter(|slug| !slug.is_empty())
+                .map(|slug| {
+                    topics.iter().find(|t| &t.slug == slug).cloned().unwrap_or_else(|| Topic {
+                        name: slug.clone(),
+                        slug: slug.clone(),
+                    })
+                })
+                .collect(),
+            mode,
+        }
+    }
+
+    pub fn contains(&self, topic: &Topic) -> bool {
+        self.topics.iter().any(|t| t.slug == topic.slug)
+    }
+
+    /// Adds `topic` if it isn't selected yet, otherwise removes it.
+    pub fn toggle(&mut self, topic: &Topic) {
+        if self.contains(topic) {
+            self.topics.retain(|t| t.slug != topic.slug);
+        } else {
+            self.topics.push(topic.clone());
+        }
+    }
+}
+
 impl DifficultyFilter {
     pub fn to_str(self) -> &'static str {
         match self {
             DifficultyFilter::All => "All",
-            DifficultyFilter::Specific(difficulty) => match difficulty {
-                Difficulty::Easy => "Easy",
-                Difficulty::Medium => "Medium",
-                Difficulty::Hard => "Hard",
-            },
+            DifficultyFilter::Specific(difficulty) => difficulty.to_str(),
         }
     }
     pub fn default() -> Self {
@@ -144,6 +634,67 @@ impl DifficultyFilter {
     }
 }
 
+impl fmt::Display for QuestionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuestionSource::Synced => write!(f, "synced"),
+            QuestionSource::Imported => write!(f, "imported"),
+            QuestionSource::Pack(name) => write!(f, "pack:{name}"),
+        }
+    }
+}
+
+impl std::str::FromStr for QuestionSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "synced" => Ok(QuestionSource::Synced),
+            "imported" => Ok(QuestionSource::Imported),
+            other => match other.strip_prefix("pack:") {
+                Some(name) if !name.is_empty() => Ok(QuestionSource::Pack(name.to_string())),
+                _ => Err(format!("Invalid source '{}', expected synced, imported or pack:<name>", s)),
+            },
+        }
+    }
+}
+
+impl Serialize for QuestionSource {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for QuestionSource {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for SourceFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SourceFilter::All => write!(f, "All Sources"),
+            SourceFilter::Specific(QuestionSource::Synced) => write!(f, "Synced"),
+            SourceFilter::Specific(QuestionSource::Imported) => write!(f, "Imported"),
+            SourceFilter::Specific(QuestionSource::Pack(name)) => write!(f, "Pack: {name}"),
+        }
+    }
+}
+
+impl std::str::FromStr for SourceFilter {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(SourceFilter::All),
+            _ => s.parse().map(SourceFilter::Specific),
+        }
+    }
+}
+
 impl std::str::FromStr for DifficultyFilter {
     type Err = String;
 
@@ -0,0 +1,82 @@
+use std::time::Duration;
+
+use crossterm::event::{Event, EventStream};
+use futures::StreamExt;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::JoinHandle;
+
+use crate::types::AppState;
+
+/// A change computed off the UI task, applied to the state between events.
+pub type StateUpdate = Box<dyn FnOnce(&mut AppState) + Send>;
+
+pub enum AppEvent {
+    /// Keys, resizes and other terminal input.
+    Input(Event),
+    /// Sent every tick so time-based state (e.g. a running search) gets redrawn.
+    Tick,
+    /// Pushed by background tasks such as lazy loads.
+    Update(StateUpdate),
+    /// Reading the terminal failed; the UI can't continue.
+    Error(std::io::Error),
+}
+
+/// Merges terminal input, ticks and background updates into one stream, so the
+/// UI awaits a single channel and never blocks the runtime.
+pub struct EventHandler {
+    sender: UnboundedSender<AppEvent>,
+    receiver: UnboundedReceiver<AppEvent>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl EventHandler {
+    pub fn new(tick_rate: Duration) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let tasks = vec![
+            tokio::spawn(read_input(sender.clone())),
+            tokio::spawn(tick(sender.clone(), tick_rate)),
+        ];
+        EventHandler { sender, receiver, tasks }
+    }
+
+    /// A handle for background tasks to send [`AppEvent::Update`]s with.
+    pub fn sender(&self) -> UnboundedSender<AppEvent> {
+        self.sender.clone()
+    }
+
+    pub async fn next(&mut self) -> Option<AppEvent> {
+        self.receiver.recv().await
+    }
+}
+
+impl Drop for EventHandler {
+    fn drop(&mut self) {
+        // stop reading the terminal once the UI is gone
+        for task in &self.tasks {
+            task.abort();
+        }
+    }
+}
+
+async fn read_input(sender: UnboundedSender<AppEvent>) {
+    let mut stream = EventStream::new();
+    while let Some(event) = stream.next().await {
+        let event = match event {
+            Ok(event) => AppEvent::Input(event),
+            Err(e) => AppEvent::Error(e),
+        };
+        if sender.send(event).is_err() {
+            return;
+        }
+    }
+}
+
+async fn tick(sender: UnboundedSender<AppEvent>, tick_rate: Duration) {
+    let mut interval = tokio::time::interval(tick_rate);
+    loop {
+        interval.tick().await;
+        if sender.send(AppEvent::Tick).is_err() {
+            return;
+        }
+    }
+}
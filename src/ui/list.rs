@@ -1,38 +1,74 @@
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
 use ratatui::{
     Terminal,
     Frame,
     backend::Backend,
-    crossterm::event::{self, Event, KeyCode, KeyModifiers},
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{ Modifier, Style},
-    text::{Line, Span},
+    text::{Line, Span, Text},
     widgets::{
-        Block, BorderType, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Wrap,
+        Block, BorderType, Borders, Cell, Clear, List, ListItem, ListState, Padding, Paragraph, Row,
+        Table, TableState, Wrap,
     },
 };
 
 
-use crate::utils::fuzzy_matcher::{search_questions, search_topics};
-use crate::utils::parse_html::parse_html_to_lines;
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::ops::Range;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::ui::event::{AppEvent, EventHandler, StateUpdate};
+use crate::ui::sync::start_sync;
+use crate::utils::fuzzy_matcher::search_topics;
+use crate::utils::preview_cache::PreviewCache;
 use crate::{
-    db::zuko_cli::get_all_questions,
-    types::{AppState, CurrentScreen, DifficultyFilter},
+    db::search_index::{get_search_contents, search_question_content, SNIPPET_END, SNIPPET_START},
+    db::zuko_user::{set_bookmarked, set_status},
+    types::{
+        AppState, ContentMatch, CurrentScreen, DifficultyFilter, Progress, ProgressStatus, QuestionSource, SearchMode,
+        SortOrder, SourceFilter, SyncStatus, Topic,
+    },
     utils::ui::centered_rect,
 };
 
 use crate::config::ui::{
     BACKGROUND_COLOR, BLOCK_PADDING, BORDER_COLOR, HIGHLIGHT_COLOR, HIGHLIGHT_SYMBOL,
-    POPUP_BACKGROUND_COLOR, POPUP_BORDER_COLOR, TEXT_COLOR, TITLE_TEXT_COLOR,  DARK_TEXT_COLOR, LIGHT_ORANGE
+    POPUP_BACKGROUND_COLOR, POPUP_BORDER_COLOR, TEXT_COLOR, TITLE_TEXT_COLOR,  DARK_TEXT_COLOR, LIGHT_ORANGE,
+    EASY_COLOR, MEDIUM_COLOR, HARD_COLOR,
 };
 
+/// How often background search results are picked up.
+const TICK_RATE: Duration = Duration::from_millis(33);
+const CONTENT_SEARCH_LIMIT: usize = 200;
+/// Questions below the selected one whose details are fetched ahead of time.
+const PREFETCH_AHEAD: usize = 5;
+
+// Terminal widths at which the list and preview are re-arranged.
+const NARROW_LAYOUT_WIDTH: u16 = 100;
+const WIDE_LAYOUT_WIDTH: u16 = 160;
+
+// List widths at which the optional columns appear.
+const DIFFICULTY_COLUMN_MIN_WIDTH: u16 = 40;
+const SOLVED_DATE_COLUMN_MIN_WIDTH: u16 = 60;
+const SOURCE_COLUMN_MIN_WIDTH: u16 = 70;
+const TOPICS_COLUMN_MIN_WIDTH: u16 = 80;
+
 pub async fn run_list_ui<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut AppState,
 ) -> Result<(), Box<dyn std::error::Error>> {
+    let mut events = EventHandler::new(TICK_RATE);
+    let event_sender = events.sender();
+
     // question list state
     // question list state
-    update_question_list(app);
-    let mut question_list_state: ListState = ListState::default();
+    update_question_list(app, &event_sender);
+    load_search_contents(&event_sender);
+    let mut question_list_state: TableState = TableState::default();
     question_list_state.select(Some(app.selected_index));
 
     // topic list state
@@ -45,160 +81,277 @@ pub async fn run_list_ui<B: Backend>(
     let mut difficulty_list_state: ListState = ListState::default();
     difficulty_list_state.select(Some(app.selected_difficulty_index));
 
+    // source list state
+    let mut source_list_state: ListState = ListState::default();
+    source_list_state.select(Some(app.selected_source_index));
+
+    let mut preview_cache = PreviewCache::default();
+
+    let mut redraw = true;
+
     loop {
-        // Draw all UI components via dedicated rendering functions
-        terminal.draw(|frame| {
-            draw_outer_block(frame);
-            let (question_list_area, search_area, preview_area, footer_area) =
-                compute_layout(frame);
-            draw_question_list(frame, app, question_list_area, &mut question_list_state);
-            draw_search_input(frame, app, search_area);
-            draw_question_preview(frame, app, preview_area);
-            draw_footer(frame, app, footer_area);
-            if let CurrentScreen::TopicList = app.current_screen {
-                draw_topic_popup(frame, app, &mut topic_list_state);
-            }
-            if let CurrentScreen::DifficultyFilter = app.current_screen {
-                draw_difficulty_popup(frame, app, &mut difficulty_list_state);
-            }
-        })?;
+        if app.results_stale {
+            app.results_stale = false;
+            update_question_list(app, &event_sender);
+            question_list_state.select(Some(app.selected_index));
+            update_topic_list(app);
+            topic_list_state.select(Some(app.selected_topic_index));
+            // packs may have come or gone
+            source_list_state.select(Some(app.selected_source_index));
+            // statements may have changed with the questions
+            preview_cache.clear();
+        }
+        if apply_search_results(app) {
+            question_list_state.select(Some(app.selected_index));
+            redraw = true;
+        }
+        prefetch_details(app, &event_sender);
+        let window = prefetch_window(app);
+        preview_cache.retain(
+            app.filtered_question_indices[window]
+                .iter()
+                .map(|&idx| app.all_questions[idx].title_slug.as_str()),
+        );
+
+        if redraw {
+            // Draw all UI components via dedicated rendering functions
+            terminal.draw(|frame| {
+                draw_outer_block(frame);
+                let layout = compute_layout(frame);
+                draw_filter_chips(frame, app, layout.filter_chips);
+                draw_question_list(frame, app, layout.question_list, &mut question_list_state);
+                draw_search_input(frame, app, layout.search);
+                draw_question_preview(frame, app, layout.preview, &mut preview_cache);
+                draw_footer(frame, app, layout.footer);
+                if let CurrentScreen::TopicList = app.current_screen {
+                    draw_topic_popup(frame, app, &mut topic_list_state);
+                }
+                if let CurrentScreen::DifficultyFilter = app.current_screen {
+                    draw_difficulty_popup(frame, app, &mut difficulty_list_state);
+                }
+                if let CurrentScreen::SourceFilter = app.current_screen {
+                    draw_source_popup(frame, app, &mut source_list_state);
+                }
+            })?;
+        }
+        redraw = true;
 
         // --------------------------- event management ---------------------
 
-        if let Event::Key(key) = event::read()? {
-            if key.kind == event::KeyEventKind::Release {
-                // Skip events that are not KeyEventKind::Press
+        let key = match events.next().await {
+            Some(AppEvent::Input(Event::Key(key))) => key,
+            // resizes and other terminal events only need a redraw
+            Some(AppEvent::Input(_)) => continue,
+            Some(AppEvent::Update(update)) => {
+                update(app);
+                question_list_state.select(Some(app.selected_index));
                 continue;
             }
-            match app.current_screen {
-                CurrentScreen::QuestionList => {
-                    // Handle question list events
-                    match (key.code, key.modifiers) {
-                        (KeyCode::Char(c), KeyModifiers::NONE) => {
-                            app.query.push(c);
-                            app.selected_index = 0;
-                            update_question_list(app);
-                            question_list_state.select(Some(app.selected_index));
-                            app.scroll = 0;
-                        }
-                        (KeyCode::Backspace, KeyModifiers::NONE) => {
-                            app.query.pop();
-                            app.selected_index = 0;
-                            update_question_list(app);
-                            question_list_state.select(Some(app.selected_index));
-                            app.scroll = 0;
-                        }
-                        (KeyCode::Up, KeyModifiers::NONE) => {
-                            if app.selected_index > 0 {
-                                app.selected_index -= 1;
-                                question_list_state.select(Some(app.selected_index));
-                                app.scroll = 0;
-                            }
-                        }
-                        (KeyCode::Down, KeyModifiers::NONE) => {
-                            if app.selected_index + 1 < app.filtered_question_indices.len() {
-                                app.selected_index += 1;
-                                question_list_state.select(Some(app.selected_index));
-                                app.scroll = 0;
-                            }
-                        }
-                        (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
-                            break;
-                        }
-                        // ctrl + t to toggle topic filter popup
-                        (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
-                            app.current_screen = CurrentScreen::TopicList;
-                        }
-                        // ctrl + d to toggle difficulty filter popup
-                        (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
-                            app.current_screen = CurrentScreen::DifficultyFilter;
+            Some(AppEvent::Tick) => {
+                // finished searches are applied above; otherwise only the indicators may change
+                redraw = app.is_searching() || app.sync_status.is_running();
+                continue;
+            }
+            Some(AppEvent::Error(e)) => return Err(e.into()),
+            None => break,
+        };
+
+        if key.kind == KeyEventKind::Release {
+            // Skip events that are not KeyEventKind::Press
+            continue;
+        }
+        app.error_message = None;
+        match app.current_screen {
+            CurrentScreen::QuestionList => {
+                // Handle question list events
+                match (key.code, key.modifiers) {
+                    // shift is allowed so query syntax like `!solved` or `"phrase"` can be typed
+                    (KeyCode::Char(c), KeyModifiers::NONE | KeyModifiers::SHIFT) => {
+                        app.query.push(c);
+                        app.selected_index = 0;
+                        update_question_list(app, &event_sender);
+                        question_list_state.select(Some(app.selected_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Backspace, KeyModifiers::NONE) => {
+                        app.query.pop();
+                        app.selected_index = 0;
+                        update_question_list(app, &event_sender);
+                        question_list_state.select(Some(app.selected_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Up, KeyModifiers::NONE) if app.selected_index > 0 => {
+                        app.selected_index -= 1;
+                        question_list_state.select(Some(app.selected_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Down, KeyModifiers::NONE) if app.selected_index + 1 < app.filtered_question_indices.len() => {
+                        app.selected_index += 1;
+                        question_list_state.select(Some(app.selected_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Char('c'), KeyModifiers::CONTROL) => {
+                        break;
+                    }
+                    // ctrl + t to toggle topic filter popup
+                    (KeyCode::Char('t'), KeyModifiers::CONTROL) => {
+                        app.topic_draft = app.selected_topics.clone();
+                        app.current_screen = CurrentScreen::TopicList;
+                    }
+                    // ctrl + d to toggle difficulty filter popup
+                    (KeyCode::Char('d'), KeyModifiers::CONTROL) => {
+                        app.current_screen = CurrentScreen::DifficultyFilter;
+                    }
+                    // ctrl + p to toggle source filter popup
+                    (KeyCode::Char('p'), KeyModifiers::CONTROL) => {
+                        app.current_screen = CurrentScreen::SourceFilter;
+                    }
+                    // ctrl + f to switch between fuzzy and full-text search
+                    (KeyCode::Char('f'), KeyModifiers::CONTROL) => {
+                        app.search_mode = match app.search_mode {
+                            SearchMode::Fuzzy => SearchMode::Content,
+                            SearchMode::Content => SearchMode::Fuzzy,
+                        };
+                        app.selected_index = 0;
+                        update_question_list(app, &event_sender);
+                        question_list_state.select(Some(app.selected_index));
+                    }
+                    // ctrl + o to cycle the sort order
+                    (KeyCode::Char('o'), KeyModifiers::CONTROL) => {
+                        app.sort_order = app.sort_order.next();
+                        if app.sort_order == SortOrder::Random {
+                            app.sort_seed = random_seed();
                         }
-                        // implement scroll functionality
-                        _ => {}
+                        app.selected_index = 0;
+                        update_question_list(app, &event_sender);
+                        question_list_state.select(Some(app.selected_index));
                     }
+                    // ctrl + r to sync with the remote database in the background
+                    (KeyCode::Char('r'), KeyModifiers::CONTROL) => {
+                        start_sync(&mut app.sync_status, &event_sender);
+                    }
+                    // ctrl + b to bookmark the selected question
+                    (KeyCode::Char('b'), KeyModifiers::CONTROL) => {
+                        toggle_bookmark(app, &event_sender);
+                    }
+                    // ctrl + s to toggle the solved state of the selected question
+                    (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
+                        toggle_solved(app, &event_sender);
+                    }
+                    // implement scroll functionality
+                    _ => {}
                 }
-                CurrentScreen::TopicList => {
-                    // Handle topic list events
-                    match (key.code, key.modifiers) {
-                        (KeyCode::Char(c), KeyModifiers::NONE) => {
-                            app.topic_query.push(c);
-                            update_topic_list(app);
-                            topic_list_state.select(Some(app.selected_topic_index));
-                            app.scroll = 0;
-                        }
-                        (KeyCode::Backspace, KeyModifiers::NONE) => {
-                            app.topic_query.pop();
-                            update_topic_list(app);
-                            topic_list_state.select(Some(app.selected_topic_index));
-                            app.scroll = 0;
-                        }
-                        (KeyCode::Up, KeyModifiers::NONE) => {
-                            if app.selected_topic_index > 0 {
-                                app.selected_topic_index -= 1;
-                                topic_list_state.select(Some(app.selected_topic_index));
-                                app.scroll = 0;
-                            }
-                        }
-                        (KeyCode::Down, KeyModifiers::NONE) => {
-                            if app.selected_topic_index + 1 < app.filtered_topic_indices.len() {
-                                app.selected_topic_index += 1;
-                                topic_list_state.select(Some(app.selected_topic_index));
-                                app.scroll = 0;
-                            }
-                        }
-                        (KeyCode::Enter, KeyModifiers::NONE) => {
-                            
-                            if let Some(selected_topic) = app
-                                .filtered_topic_indices
-                                .get(app.selected_topic_index)
-                                .and_then(|&idx| app.all_topics.get(idx).cloned())
-                            {
-                                app.selected_topic = Some(selected_topic);
-                                filter_questions_by_topic_and_difficulty(app).await;
-                                app.topic_query.clear();
-                                update_topic_list(app);
-                                update_question_list(app);
+            }
+            CurrentScreen::TopicList => {
+                // Handle topic list events
+                match (key.code, key.modifiers) {
+                    (KeyCode::Char(c), KeyModifiers::NONE) => {
+                        app.topic_query.push(c);
+                        update_topic_list(app);
+                        topic_list_state.select(Some(app.selected_topic_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Backspace, KeyModifiers::NONE) => {
+                        app.topic_query.pop();
+                        update_topic_list(app);
+                        topic_list_state.select(Some(app.selected_topic_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Up, KeyModifiers::NONE) if app.selected_topic_index > 0 => {
+                        app.selected_topic_index -= 1;
+                        topic_list_state.select(Some(app.selected_topic_index));
+                        app.scroll = 0;
+                    }
+                    (KeyCode::Down, KeyModifiers::NONE) if app.selected_topic_index + 1 < app.filtered_topic_indices.len() => {
+                        app.selected_topic_index += 1;
+                        topic_list_state.select(Some(app.selected_topic_index));
+                        app.scroll = 0;
+                    }
+                    // tab toggles the highlighted topic in the selection
+                    (KeyCode::Tab, KeyModifiers::NONE) => {
+                        if let Some(topic) = highlighted_topic(app) {
+                            if topic.slug.is_empty() {
+                                // the "All Topics" entry clears the selection
+                                app.topic_draft.topics.clear();
+                            } else {
+                                app.topic_draft.toggle(&topic);
                             }
-                            app.selected_index = 0;
-                            question_list_state.select(Some(app.selected_index));
-                            app.current_screen = CurrentScreen::QuestionList;
                         }
-                        (KeyCode::Esc, KeyModifiers::NONE) => {
-                            app.topic_query.clear();
-                            update_topic_list(app);
-                            app.current_screen = CurrentScreen::QuestionList;
+                    }
+                    // ctrl + a switches between "any of" and "all of"
+                    (KeyCode::Char('a'), KeyModifiers::CONTROL) => {
+                        app.topic_draft.mode = app.topic_draft.mode.toggle();
+                    }
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        // without toggled topics, Enter picks just the highlighted one
+                        if app.topic_draft.topics.is_empty()
+                            && let Some(topic) = highlighted_topic(app)
+                            && !topic.slug.is_empty()
+                        {
+                            app.topic_draft.topics.push(topic);
                         }
-                        _ => {}
+                        app.selected_topics = app.topic_draft.clone();
+                        apply_filters(app, &event_sender);
+                        app.topic_query.clear();
+                        update_topic_list(app);
+                        app.selected_index = 0;
+                        question_list_state.select(Some(app.selected_index));
+                        app.current_screen = CurrentScreen::QuestionList;
+                    }
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        app.topic_query.clear();
+                        update_topic_list(app);
+                        app.current_screen = CurrentScreen::QuestionList;
                     }
+                    _ => {}
                 }
-                CurrentScreen::DifficultyFilter => {
-                    // Handle difficulty filter events
-                    match (key.code, key.modifiers) {
-                        (KeyCode::Up, KeyModifiers::NONE) => {
-                            if app.selected_difficulty_index > 0 {
-                                app.selected_difficulty_index -= 1;
-
-                                difficulty_list_state.select(Some(app.selected_difficulty_index));
-                            }
-                        }
-                        (KeyCode::Down, KeyModifiers::NONE) => {
-                            if app.selected_difficulty_index + 1 < app.difficulties.len() {
-                                app.selected_difficulty_index += 1;
-                                difficulty_list_state.select(Some(app.selected_difficulty_index));
-                            }
-                        }
-                        (KeyCode::Enter, KeyModifiers::NONE) => {
-                            app.selected_index = 0;
-                            question_list_state.select(Some(app.selected_index));
-                            update_difficulty_list(app);
-                            filter_questions_by_topic_and_difficulty(app).await;
-                            app.current_screen = CurrentScreen::QuestionList;
-                        }
-                        (KeyCode::Esc, KeyModifiers::NONE) => {
-                            app.current_screen = CurrentScreen::QuestionList;
-                        }
-                        _ => {}
+            }
+            CurrentScreen::DifficultyFilter => {
+                // Handle difficulty filter events
+                match (key.code, key.modifiers) {
+                    (KeyCode::Up, KeyModifiers::NONE) if app.selected_difficulty_index > 0 => {
+                        app.selected_difficulty_index -= 1;
+                        difficulty_list_state.select(Some(app.selected_difficulty_index));
+                    }
+                    (KeyCode::Down, KeyModifiers::NONE) if app.selected_difficulty_index + 1 < app.difficulties.len() => {
+                        app.selected_difficulty_index += 1;
+                        difficulty_list_state.select(Some(app.selected_difficulty_index));
+                    }
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        app.selected_index = 0;
+                        question_list_state.select(Some(app.selected_index));
+                        update_difficulty_list(app);
+                        apply_filters(app, &event_sender);
+                        app.current_screen = CurrentScreen::QuestionList;
+                    }
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        app.current_screen = CurrentScreen::QuestionList;
                     }
+                    _ => {}
+                }
+            }
+            CurrentScreen::SourceFilter => {
+                // Handle source filter events
+                match (key.code, key.modifiers) {
+                    (KeyCode::Up, KeyModifiers::NONE) if app.selected_source_index > 0 => {
+                        app.selected_source_index -= 1;
+                        source_list_state.select(Some(app.selected_source_index));
+                    }
+                    (KeyCode::Down, KeyModifiers::NONE) if app.selected_source_index + 1 < app.sources.len() => {
+                        app.selected_source_index += 1;
+                        source_list_state.select(Some(app.selected_source_index));
+                    }
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        app.selected_index = 0;
+                        question_list_state.select(Some(app.selected_index));
+                        app.selected_source = app.sources[app.selected_source_index].clone();
+                        apply_filters(app, &event_sender);
+                        app.current_screen = CurrentScreen::QuestionList;
+                    }
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        app.current_screen = CurrentScreen::QuestionList;
+                    }
+                    _ => {}
                 }
             }
         }
@@ -221,48 +374,201 @@ fn draw_outer_block(frame: &mut Frame) {
     frame.render_widget(zuko_area, frame.area());
 }
 
-/// Computes the main layout and returns (question_list_area, search_area, preview_area, footer_area).
-fn compute_layout(frame: &Frame) -> (ratatui::layout::Rect, ratatui::layout::Rect, ratatui::layout::Rect, ratatui::layout::Rect) {
+/// Areas of the question browser, top to bottom.
+struct QuestionListLayout {
+    filter_chips: Rect,
+    question_list: Rect,
+    search: Rect,
+    preview: Rect,
+    footer: Rect,
+}
+
+/// Computes the main layout of the question browser.
+///
+/// Narrow terminals stack the preview below the list; wide ones give the list
+/// more room for its extra columns.
+fn compute_layout(frame: &Frame) -> QuestionListLayout {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(2)
         .constraints([Constraint::Min(3), Constraint::Length(3)])
         .split(frame.area());
 
+    let width = chunks[0].width;
+    let (direction, list_percentage) = if width < NARROW_LAYOUT_WIDTH {
+        (Direction::Vertical, 50)
+    } else if width >= WIDE_LAYOUT_WIDTH {
+        (Direction::Horizontal, 55)
+    } else {
+        (Direction::Horizontal, 40)
+    };
+
     let question_list_ui_chunk = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .direction(direction)
+        .constraints([
+            Constraint::Percentage(list_percentage),
+            Constraint::Percentage(100 - list_percentage),
+        ])
         .split(chunks[0]);
 
     let question_list_chunk = Layout::default()
         .direction(Direction::Vertical)
-        .constraints([Constraint::Min(3), Constraint::Length(3)])
+        .constraints([Constraint::Length(1), Constraint::Min(3), Constraint::Length(3)])
         .split(question_list_ui_chunk[0]);
 
-    (question_list_chunk[0], question_list_chunk[1], question_list_ui_chunk[1], chunks[1])
+    QuestionListLayout {
+        filter_chips: question_list_chunk[0],
+        question_list: question_list_chunk[1],
+        search: question_list_chunk[2],
+        preview: question_list_ui_chunk[1],
+        footer: chunks[1],
+    }
 }
 
 // ── Individual panel renderers ───────────────────────────────────────────────
 
-/// Renders the question list panel with the current selection.
-fn draw_question_list(frame: &mut Frame, app: &AppState, area: ratatui::layout::Rect, state: &mut ListState) {
-    let items: Vec<ListItem> = app
+/// Renders the active topic, difficulty and source filters as chips above the list.
+fn draw_filter_chips(frame: &mut Frame, app: &AppState, area: Rect) {
+    let chip_style = Style::default().bg(LIGHT_ORANGE).fg(DARK_TEXT_COLOR);
+    let mut spans = Vec::new();
+
+    if !app.selected_topics.topics.is_empty() {
+        if app.selected_topics.topics.len() > 1 {
+            spans.push(Span::styled(
+                format!("{}: ", app.selected_topics.mode),
+                Style::default().fg(TEXT_COLOR),
+            ));
+        }
+        for topic in &app.selected_topics.topics {
+            spans.push(Span::styled(format!(" {} ", topic.name), chip_style));
+            spans.push(Span::raw(" "));
+        }
+    }
+    if let DifficultyFilter::Specific(difficulty) = app.selected_difficulty {
+        spans.push(Span::styled(format!(" {} ", difficulty), chip_style));
+        spans.push(Span::raw(" "));
+    }
+    if let SourceFilter::Specific(_) = app.selected_source {
+        spans.push(Span::styled(format!(" {} ", app.selected_source), chip_style));
+    }
+    if spans.is_empty() {
+        // sources only matter once something besides the synced set is loaded
+        let placeholder = if has_several_sources(app) {
+            "All Topics · All Difficulties · All Sources"
+        } else {
+            "All Topics · All Difficulties"
+        };
+        spans.push(Span::styled(placeholder, Style::default().fg(BORDER_COLOR)));
+    }
+
+    frame.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+/// Renders the question list as a table whose columns depend on the available width.
+fn draw_question_list(frame: &mut Frame, app: &AppState, area: Rect, state: &mut TableState) {
+    // borders plus the highlight symbol
+    let inner_width = area.width.saturating_sub(4);
+    let show_difficulty = inner_width >= DIFFICULTY_COLUMN_MIN_WIDTH;
+    let show_solved_date = inner_width >= SOLVED_DATE_COLUMN_MIN_WIDTH;
+    let show_topics = inner_width >= TOPICS_COLUMN_MIN_WIDTH;
+    let show_source = inner_width >= SOURCE_COLUMN_MIN_WIDTH && has_several_sources(app);
+
+    let mut header = vec![Cell::from(" "), Cell::from("Title")];
+    let mut widths = vec![Constraint::Length(1), Constraint::Fill(3)];
+    if show_difficulty {
+        header.push(Cell::from("Level"));
+        widths.push(Constraint::Length(6));
+    }
+    if show_topics {
+        header.push(Cell::from("Topics"));
+        widths.push(Constraint::Fill(2));
+    }
+    if show_source {
+        header.push(Cell::from("Source"));
+        widths.push(Constraint::Length(12));
+    }
+    if show_solved_date {
+        header.push(Cell::from("Solved"));
+        widths.push(Constraint::Length(10));
+    }
+    header.push(Cell::from(" "));
+    widths.push(Constraint::Length(1));
+
+    let window = visible_window(app.filtered_question_indices.len(), area, state);
+    let rows: Vec<Row> = app
         .filtered_question_indices
         .iter()
-        .filter_map(|&idx| app.all_questions.get(idx))
-        .map(|q| ListItem::new(Line::from(Span::styled(q.title.clone(), Style::default()))))
+        .zip(&app.title_highlights)
+        .enumerate()
+        .skip(window.start)
+        .take(window.len())
+        .filter_map(|(pos, (&idx, highlights))| {
+            app.all_questions
+                .get(idx)
+                .map(|q| (q, highlights, app.content_snippets.get(pos)))
+        })
+        .map(|(q, highlights, snippet)| {
+            let progress = app.progress.get(&q.title_slug);
+            let status = match progress.map(|p| p.status) {
+                Some(ProgressStatus::Solved) => "✔",
+                Some(ProgressStatus::Attempted) => "◐",
+                _ => " ",
+            };
+
+            let mut title = vec![Line::from(highlight_matches(&q.title, highlights))];
+            title.extend(snippet.map(|snippet| snippet_line(snippet)));
+            let height = title.len() as u16;
+
+            let mut cells = vec![
+                Cell::from(Span::styled(status, Style::default().fg(LIGHT_ORANGE))),
+                Cell::from(Text::from(title)),
+            ];
+            if show_difficulty {
+                cells.push(Cell::from(difficulty_badge(q.difficulty.as_deref())));
+            }
+            if show_topics {
+                let topics = q
+                    .topic
+                    .iter()
+                    .flatten()
+                    .map(|t| t.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                cells.push(Cell::from(Span::styled(topics, Style::default().fg(BORDER_COLOR))));
+            }
+            if show_source {
+                cells.push(Cell::from(source_label(&q.source)));
+            }
+            if show_solved_date {
+                let solved_on = progress
+                    .and_then(|p| p.last_solved_at.as_deref())
+                    .map_or("—", |at| at.get(..10).unwrap_or(at));
+                cells.push(Cell::from(Span::styled(solved_on.to_string(), Style::default().fg(BORDER_COLOR))));
+            }
+            let bookmark = if progress.is_some_and(|p| p.bookmarked) { "★" } else { " " };
+            cells.push(Cell::from(Span::styled(bookmark, Style::default().fg(HIGHLIGHT_COLOR))));
+
+            Row::new(cells).height(height)
+        })
         .collect();
 
-    let list = List::new(items)
+    let table = Table::new(rows, widths)
+        .header(
+            Row::new(header).style(
+                Style::default()
+                    .fg(TITLE_TEXT_COLOR)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        )
+        .column_spacing(1)
         .block(
             Block::default()
                 .title(format!(
-                    " Questions ({} - {})",
-                    app.selected_topic
-                        .as_ref()
-                        .map_or("All Topics", |t| &t.name),
-                    app.selected_difficulty
+                    " Questions ({}){}",
+                    app.filtered_question_indices.len(),
+                    if app.is_searching() { " searching…" } else { "" }
                 ))
+                .title_bottom(Line::from(format!(" Sort: {} ", app.sort_order)).alignment(Alignment::Right))
                 .title_style(Style::default().fg(TITLE_TEXT_COLOR))
                 .borders(Borders::ALL)
                 .padding(Padding::new(0, 0, 1, 0))
@@ -270,13 +576,98 @@ fn draw_question_list(frame: &mut Frame, app: &AppState, area: ratatui::layout::
                 .border_type(BorderType::Rounded),
         )
         .highlight_symbol(HIGHLIGHT_SYMBOL)
-        .highlight_style(
+        .row_highlight_style(
             Style::default()
                 .fg(HIGHLIGHT_COLOR)
                 .add_modifier(Modifier::BOLD),
         );
 
-    frame.render_stateful_widget(list, area, state);
+    // the table only sees the window, so the state is shifted into it and back
+    let mut window_state = TableState::default()
+        .with_offset(state.offset().saturating_sub(window.start))
+        .with_selected(state.selected().map(|selected| selected.saturating_sub(window.start)));
+    frame.render_stateful_widget(table, area, &mut window_state);
+    *state.offset_mut() = window.start + window_state.offset();
+}
+
+/// The rows of a list of `len` that can be on screen: the table scrolls no
+/// further than it takes to show the selected row, and every row is at least
+/// one line high.
+fn visible_window(len: usize, area: Rect, state: &TableState) -> Range<usize> {
+    // borders, the padding and the header
+    let height = (area.height.saturating_sub(4) as usize).max(1);
+    let selected = state.selected().unwrap_or(0).min(len.saturating_sub(1));
+    let start = state.offset().min(selected).max((selected + 1).saturating_sub(height));
+    start..(selected + height).min(len)
+}
+
+/// Whether the loaded questions come from more than the synced set.
+fn has_several_sources(app: &AppState) -> bool {
+    // the first entry is "All"
+    app.sources.len() > 2
+}
+
+/// Label for the source column; synced questions are left blank to keep the
+/// other sources easy to spot.
+fn source_label(source: &QuestionSource) -> Span<'static> {
+    match source {
+        QuestionSource::Synced => Span::raw(""),
+        QuestionSource::Imported => Span::styled("imported", Style::default().fg(LIGHT_ORANGE)),
+        QuestionSource::Pack(name) => Span::styled(name.clone(), Style::default().fg(HIGHLIGHT_COLOR)),
+    }
+}
+
+/// Short, coloured label for a question's difficulty.
+fn difficulty_badge(difficulty: Option<&str>) -> Span<'static> {
+    match difficulty.map(str::to_uppercase).as_deref() {
+        Some("EASY") => Span::styled("Easy", Style::default().fg(EASY_COLOR)),
+        Some("MEDIUM") => Span::styled("Med.", Style::default().fg(MEDIUM_COLOR)),
+        Some("HARD") => Span::styled("Hard", Style::default().fg(HARD_COLOR)),
+        _ => Span::raw("—"),
+    }
+}
+
+/// Splits `text` into spans, colouring the chars at the (sorted) `indices`.
+/// Matches are also underlined so they stay visible on the highlighted row.
+fn highlight_matches(text: &str, indices: &[u32]) -> Vec<Span<'static>> {
+    let matched_style = Style::default()
+        .fg(HIGHLIGHT_COLOR)
+        .add_modifier(Modifier::UNDERLINED);
+
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+    let mut next_index = indices.iter().peekable();
+
+    for (i, c) in text.chars().enumerate() {
+        let matched = next_index.next_if(|&&idx| idx as usize == i).is_some();
+        if matched != current_matched && !current.is_empty() {
+            let style = if current_matched { matched_style } else { Style::default() };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = matched;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_matched { matched_style } else { Style::default() };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+/// Renders a full-text snippet as a dimmed, indented line with the matched terms highlighted.
+fn snippet_line(snippet: &str) -> Line<'static> {
+    let mut spans = vec![Span::raw("    ")];
+    for (i, part) in snippet.split([SNIPPET_START, SNIPPET_END]).enumerate() {
+        // odd parts sit between a start and an end marker
+        let style = if i % 2 == 1 {
+            Style::default().fg(HIGHLIGHT_COLOR)
+        } else {
+            Style::default().fg(BORDER_COLOR)
+        };
+        spans.push(Span::styled(part.to_string(), style));
+    }
+    Line::from(spans)
 }
 
 /// Renders the fuzzy-search input box below the question list.
@@ -284,7 +675,17 @@ fn draw_search_input(frame: &mut Frame, app: &AppState, area: ratatui::layout::R
     let search_input = Paragraph::new(app.query.clone())
         .block(
             Block::default()
-                .title(" Search ")
+                .title(match app.search_mode {
+                    SearchMode::Fuzzy => " Search ",
+                    SearchMode::Content => " Search Statements ",
+                })
+                .title_bottom(
+                    Line::from(match app.search_mode {
+                        SearchMode::Fuzzy => " t:topic d:level is:solved !bookmarked \"phrase\" ",
+                        SearchMode::Content => " words \"exact phrase\" ",
+                    })
+                    .alignment(Alignment::Right),
+                )
                 .title_style(Style::default().fg(TITLE_TEXT_COLOR))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(BORDER_COLOR))
@@ -297,16 +698,49 @@ fn draw_search_input(frame: &mut Frame, app: &AppState, area: ratatui::layout::R
 }
 
 /// Renders the right-hand question preview panel.
-fn draw_question_preview(frame: &mut Frame, app: &AppState, area: ratatui::layout::Rect) {
-    let question_content = app
-        .filtered_question_indices
-        .get(app.selected_index)
-        .and_then(|&idx| app.all_questions.get(idx))
-        .map(|q| q.content.clone())
-        .unwrap_or_default();
+fn draw_question_preview(
+    frame: &mut Frame,
+    app: &AppState,
+    area: ratatui::layout::Rect,
+    cache: &mut PreviewCache,
+) {
+    // inside the borders and the padding
+    let width = area.width.saturating_sub(4);
+    let placeholder: Vec<Line>;
+    let (lines, limits): (&[Line], &[Line]) = match selected_question_slug(app) {
+        Some(slug) => match (app.details.get(&slug), app.details.error(&slug)) {
+            (Some(question), _) => {
+                let preview = cache.preview(question, width);
+                (&preview.statement, &preview.limits)
+            }
+            (None, Some(error)) => {
+                placeholder = vec![Line::styled(
+                    format!("Failed to load question: {error}"),
+                    Style::default().fg(HARD_COLOR),
+                )];
+                (&placeholder, &[])
+            }
+            (None, None) => {
+                placeholder = vec![Line::styled("Loading…", Style::default().fg(TITLE_TEXT_COLOR))];
+                (&placeholder, &[])
+            }
+        },
+        None => (&[], &[]),
+    };
 
-    let lines = parse_html_to_lines(&question_content);
-    let paragraph = Paragraph::new(lines.to_vec())
+    // the constraints get a panel below the statement, unless it would crowd it out
+    let limits_height = limits.len() as u16 + 2;
+    let (area, limits_area) = if !limits.is_empty() && limits_height <= area.height / 3 {
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(limits_height)])
+            .split(area);
+        (chunks[0], Some(chunks[1]))
+    } else {
+        (area, None)
+    };
+
+    let paragraph = Paragraph::new(borrowed_lines(lines, area.height))
         .block(
             Block::default()
                 .title(" Question Preview ")
@@ -316,9 +750,37 @@ fn draw_question_preview(frame: &mut Frame, app: &AppState, area: ratatui::layou
                 .border_type(BorderType::Rounded)
                 .padding(Padding::uniform(1)),
         )
-        .wrap(Wrap { trim: true });
+        // statements already fit, this is for long loading errors
+        .wrap(Wrap { trim: false });
 
     frame.render_widget(paragraph, area);
+
+    if let Some(limits_area) = limits_area {
+        let panel = Paragraph::new(borrowed_lines(limits, limits_area.height)).block(
+            Block::default()
+                .title(" Constraints ")
+                .title_style(Style::default().fg(TITLE_TEXT_COLOR))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(BORDER_COLOR))
+                .border_type(BorderType::Rounded)
+                .padding(Padding::horizontal(1)),
+        );
+        frame.render_widget(panel, limits_area);
+    }
+}
+
+/// The first `height` of `lines`, borrowing their text so a frame doesn't copy
+/// the whole statement.
+fn borrowed_lines<'a>(lines: &'a [Line], height: u16) -> Vec<Line<'a>> {
+    lines
+        .iter()
+        .take(height as usize)
+        .map(|line| Line {
+            style: line.style,
+            alignment: line.alignment,
+            spans: line.spans.iter().map(|span| Span::styled(span.content.as_ref(), span.style)).collect(),
+        })
+        .collect()
 }
 
 /// Renders the footer bar with keybinding hints.
@@ -348,13 +810,25 @@ fn draw_topic_popup(frame: &mut Frame, app: &AppState, state: &mut ListState) {
         .filtered_topic_indices
         .iter()
         .filter_map(|&idx| app.all_topics.get(idx))
-        .map(|t| ListItem::new(Line::from(Span::styled(t.name.clone(), Style::default()))))
+        .map(|t| {
+            let checked = if t.slug.is_empty() {
+                app.topic_draft.topics.is_empty()
+            } else {
+                app.topic_draft.contains(t)
+            };
+            let checkbox = if checked { "[x] " } else { "[ ] " };
+            ListItem::new(Line::from(vec![
+                Span::styled(checkbox, Style::default().fg(LIGHT_ORANGE)),
+                Span::styled(t.name.clone(), Style::default()),
+            ]))
+        })
         .collect();
 
     let topic_list = List::new(topic_items)
         .block(
             Block::default()
-                .title(" Select Topic ")
+                .title(format!(" Select Topics ({}) ", app.topic_draft.mode))
+                .title_bottom(Line::from(" Tab: toggle / Ctrl + A: any/all ").alignment(Alignment::Right))
                 .title_style(Style::default().fg(TITLE_TEXT_COLOR))
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(BORDER_COLOR))
@@ -430,13 +904,202 @@ fn draw_difficulty_popup(frame: &mut Frame, app: &AppState, state: &mut ListStat
     frame.render_stateful_widget(difficulty_list, difficulty_chunks[0], state);
 }
 
+/// Renders the source-filter popup over the main UI.
+fn draw_source_popup(frame: &mut Frame, app: &AppState, state: &mut ListState) {
+    let source_filter_block = Block::default().style(Style::default().bg(POPUP_BACKGROUND_COLOR));
+
+    let source_popup_area = centered_rect(25, 30, frame.area());
+    frame.render_widget(Clear, source_popup_area);
+    frame.render_widget(source_filter_block, source_popup_area);
+
+    let source_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Min(3)])
+        .split(source_popup_area);
+
+    let source_items: Vec<ListItem> = app
+        .sources
+        .iter()
+        .map(|s| ListItem::new(Line::from(Span::styled(s.to_string(), Style::default()))))
+        .collect();
+
+    let source_list = List::new(source_items)
+        .block(
+            Block::default()
+                .title(" Select Source ")
+                .title_style(Style::default().fg(TITLE_TEXT_COLOR))
+                .padding(Padding::new(1, 1, 1, 1))
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(BORDER_COLOR))
+                .border_type(BorderType::Rounded),
+        )
+        .highlight_symbol(HIGHLIGHT_SYMBOL)
+        .highlight_style(
+            Style::default()
+                .fg(HIGHLIGHT_COLOR)
+                .add_modifier(Modifier::BOLD),
+        );
+
+    frame.render_stateful_widget(source_list, source_chunks[0], state);
+}
+
 // ── State update helpers ─────────────────────────────────────────────────────
 
-pub fn update_question_list(app: &mut AppState) {
-    app.filtered_question_indices = search_questions(&app.all_questions, &app.query);
+/// Re-runs the search for the current query. Searches finish in the background,
+/// see [`apply_search_results`] and [`search_content`].
+pub fn update_question_list(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    match app.search_mode {
+        SearchMode::Fuzzy => {
+            app.pending_content_query = None;
+            app.search.submit(&app.query, &app.filter_scope, &app.progress)
+        }
+        SearchMode::Content => search_content(app, events),
+    }
+}
+
+/// Applies the latest finished fuzzy search, returning whether the list changed.
+pub fn apply_search_results(app: &mut AppState) -> bool {
+    let Some(matches) = app.search.try_recv() else {
+        return false;
+    };
+    // results of a fuzzy search started before switching modes
+    if app.search_mode != SearchMode::Fuzzy {
+        return false;
+    }
+    (app.filtered_question_indices, app.title_highlights) = matches
+        .into_iter()
+        .map(|m| (m.index, m.title_indices))
+        .unzip();
+    app.content_snippets.clear();
+    apply_sort_order(app);
     if app.selected_index >= app.filtered_question_indices.len() {
         app.selected_index = 0;
     }
+    true
+}
+
+/// Starts a full-text search for the current query; its results come back
+/// through `events`, see [`apply_content_matches`].
+fn search_content(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    if app.query.trim().is_empty() {
+        app.pending_content_query = None;
+        app.filtered_question_indices = app.filter_scope.to_vec();
+        app.title_highlights = vec![Vec::new(); app.filter_scope.len()];
+        app.content_snippets.clear();
+        apply_sort_order(app);
+        return;
+    }
+
+    let query = app.query.clone();
+    app.pending_content_query = Some(query.clone());
+    let events = events.clone();
+    tokio::spawn(async move {
+        let matches = search_question_content(&query, CONTENT_SEARCH_LIMIT)
+            .await
+            .map_err(|e| e.to_string());
+        // the receiver is only gone once the UI has exited
+        let _ = events.send(AppEvent::Update(Box::new(move |app| {
+            apply_content_matches(app, &query, matches)
+        })));
+    });
+}
+
+/// Fills the list from a finished full-text search, keeping only questions inside
+/// the topic and difficulty filters. Searches overtaken by a newer one are dropped.
+fn apply_content_matches(app: &mut AppState, query: &str, matches: Result<Vec<ContentMatch>, String>) {
+    if app.search_mode != SearchMode::Content || app.pending_content_query.as_deref() != Some(query) {
+        return;
+    }
+    app.pending_content_query = None;
+    let matches = match matches {
+        Result::Ok(ms) => ms,
+        Result::Err(e) => {
+            app.error_message = Some(format!("Failed to search question content: {}", e));
+            return;
+        }
+    };
+
+    let index_by_slug: HashMap<&str, usize> = app
+        .filter_scope
+        .iter()
+        .map(|&i| (app.all_questions[i].title_slug.as_str(), i))
+        .collect();
+
+    let (indices, snippets): (Vec<usize>, Vec<String>) = matches
+        .into_iter()
+        .filter_map(|m| index_by_slug.get(m.title_slug.as_str()).map(|&i| (i, m.snippet)))
+        .unzip();
+
+    app.title_highlights = vec![Vec::new(); indices.len()];
+    app.filtered_question_indices = indices;
+    app.content_snippets = snippets;
+    apply_sort_order(app);
+    if app.selected_index >= app.filtered_question_indices.len() {
+        app.selected_index = 0;
+    }
+}
+
+/// Reorders the current results by `app.sort_order`, keeping the per-row data aligned.
+fn apply_sort_order(app: &mut AppState) {
+    let order = {
+        let question = |pos: usize| &app.all_questions[app.filtered_question_indices[pos]];
+        let progress = |pos: usize| app.progress.get(&question(pos).title_slug);
+        let last_solved = |pos: usize| progress(pos).and_then(|p| p.last_solved_at.clone());
+
+        let mut order: Vec<usize> = (0..app.filtered_question_indices.len()).collect();
+        match app.sort_order {
+            SortOrder::Relevance => return,
+            SortOrder::Title => order.sort_by_cached_key(|&pos| question(pos).title.to_lowercase()),
+            SortOrder::Difficulty => {
+                order.sort_by_key(|&pos| difficulty_rank(question(pos).difficulty.as_deref()))
+            }
+            // never-solved questions sort last
+            SortOrder::RecentlySolved => order.sort_by_cached_key(|&pos| std::cmp::Reverse(last_solved(pos))),
+            SortOrder::DueForReview => order.sort_by_cached_key(|&pos| {
+                match progress(pos).map(|p| p.status) {
+                    Some(ProgressStatus::Solved) => (0, last_solved(pos)),
+                    Some(ProgressStatus::Attempted) => (1, None),
+                    _ => (2, None),
+                }
+            }),
+            SortOrder::Random => order.sort_by_cached_key(|&pos| {
+                let mut hasher = DefaultHasher::new();
+                (app.sort_seed, &question(pos).title_slug).hash(&mut hasher);
+                hasher.finish()
+            }),
+        }
+        order
+    };
+
+    permute(&mut app.filtered_question_indices, &order);
+    permute(&mut app.title_highlights, &order);
+    permute(&mut app.content_snippets, &order);
+}
+
+/// Rearranges `items` so that position `i` holds the old `items[order[i]]`.
+/// Vectors that aren't aligned with `order` (e.g. empty snippets) are left alone.
+fn permute<T: Default>(items: &mut Vec<T>, order: &[usize]) {
+    if items.len() != order.len() {
+        return;
+    }
+    let mut old = std::mem::take(items);
+    *items = order.iter().map(|&i| std::mem::take(&mut old[i])).collect();
+}
+
+fn difficulty_rank(difficulty: Option<&str>) -> u8 {
+    match difficulty.map(str::to_uppercase).as_deref() {
+        Some("EASY") => 0,
+        Some("MEDIUM") => 1,
+        Some("HARD") => 2,
+        _ => 3,
+    }
+}
+
+fn random_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_nanos() as u64)
 }
 
 pub fn update_topic_list(app: &mut AppState) {
@@ -448,27 +1111,115 @@ pub fn update_topic_list(app: &mut AppState) {
     }
 }
 
-pub async fn filter_questions_by_topic_and_difficulty(app: &mut AppState) {
-    let selected_topic_slug = app
-        .selected_topic
-        .as_ref()
-        .map(|t| t.slug.clone())
-        .unwrap_or_default();
+/// Narrows the list to the selected topics and difficulty using the in-memory
+/// index, then re-runs the current search within them.
+pub fn apply_filters(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    app.refresh_filter_scope();
+    update_question_list(app, events);
+}
+
+/// Fetches the selected question and the next few in the background, so the
+/// preview is usually ready by the time the cursor gets there.
+fn prefetch_details(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    let window = prefetch_window(app);
+    let slugs = app.filtered_question_indices[window]
+        .iter()
+        .map(|&idx| app.all_questions[idx].title_slug.as_str());
+    app.details.request(slugs, events);
+}
 
-    // Fetch questions based on the selected topic and difficulty
+/// The positions in the filtered list of the selected question and the few after it.
+fn prefetch_window(app: &AppState) -> Range<usize> {
+    let end = (app.selected_index + PREFETCH_AHEAD + 1).min(app.filtered_question_indices.len());
+    app.selected_index.min(end)..end
+}
 
-    app.all_questions = match get_all_questions(
-        Some(selected_topic_slug),
-        Some(app.selected_difficulty.to_str().to_uppercase()),
-    )
-    .await
-    {
-        Result::Ok(qs) => qs,
-        Result::Err(e) => {
-            eprintln!("Failed to get questions from the database: {}", e);
-            return;
-        }
+/// Loads the statements for free-text terms in the background; until they
+/// arrive, terms only match titles and slugs.
+fn load_search_contents(events: &UnboundedSender<AppEvent>) {
+    let events = events.clone();
+    tokio::spawn(async move {
+        let update: StateUpdate = match get_search_contents().await.map_err(|e| e.to_string()) {
+            Ok(contents) => Box::new(move |app| app.set_search_contents(&contents)),
+            Err(e) => Box::new(move |app| {
+                app.error_message = Some(format!("Failed to load statements for search: {}", e))
+            }),
+        };
+        let _ = events.send(AppEvent::Update(update));
+    });
+}
+
+fn highlighted_topic(app: &AppState) -> Option<Topic> {
+    app.filtered_topic_indices
+        .get(app.selected_topic_index)
+        .and_then(|&idx| app.all_topics.get(idx).cloned())
+}
+
+/// Returns the title slug of the question under the cursor.
+fn selected_question_slug(app: &AppState) -> Option<String> {
+    app.filtered_question_indices
+        .get(app.selected_index)
+        .and_then(|&idx| app.all_questions.get(idx))
+        .map(|q| q.title_slug.clone())
+}
+
+/// Flips the bookmark of the selected question. The write runs in the background
+/// and the list follows once it is saved.
+pub fn toggle_bookmark(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    let Some(slug) = selected_question_slug(app) else {
+        return;
     };
+    let bookmarked = !app.progress.get(&slug).is_some_and(|p| p.bookmarked);
+
+    let events = events.clone();
+    tokio::spawn(async move {
+        let saved = set_bookmarked(&slug, bookmarked).await.map_err(|e| e.to_string());
+        let list_events = events.clone();
+        let _ = events.send(AppEvent::Update(Box::new(move |app| match saved {
+            Ok(()) => {
+                progress_entry(app, slug).bookmarked = bookmarked;
+                update_question_list(app, &list_events);
+            }
+            Err(e) => app.error_message = Some(format!("Failed to update bookmark: {}", e)),
+        })));
+    });
+}
+
+/// Marks the selected question solved, or back to attempted, like [`toggle_bookmark`].
+pub fn toggle_solved(app: &mut AppState, events: &UnboundedSender<AppEvent>) {
+    let Some(slug) = selected_question_slug(app) else {
+        return;
+    };
+    let status = match app.progress.get(&slug).map(|p| p.status) {
+        Some(ProgressStatus::Solved) => ProgressStatus::Attempted,
+        _ => ProgressStatus::Solved,
+    };
+
+    let events = events.clone();
+    tokio::spawn(async move {
+        let saved = set_status(&slug, status).await.map_err(|e| e.to_string());
+        let list_events = events.clone();
+        let _ = events.send(AppEvent::Update(Box::new(move |app| match saved {
+            Ok(solved_at) => {
+                let entry = progress_entry(app, slug);
+                entry.status = status;
+                if solved_at.is_some() {
+                    entry.last_solved_at = solved_at;
+                }
+                update_question_list(app, &list_events);
+            }
+            Err(e) => app.error_message = Some(format!("Failed to update progress: {}", e)),
+        })));
+    });
+}
+
+fn progress_entry(app: &mut AppState, slug: String) -> &mut Progress {
+    Arc::make_mut(&mut app.progress).entry(slug.clone()).or_insert_with(|| Progress {
+        title_slug: slug,
+        status: ProgressStatus::default(),
+        bookmarked: false,
+        last_solved_at: None,
+    })
 }
 
 pub fn update_difficulty_list(app: &mut AppState) {
@@ -482,7 +1233,7 @@ pub fn update_difficulty_list(app: &mut AppState) {
 }
 
 fn build_footer_paragraph(app: &AppState) -> Paragraph<'static> {
-    let current_navigation_text: Vec<Span> = vec![
+    let current_navigation_text: Vec<Span> = [
         // First segment describing current screen
         match app.current_screen {
             CurrentScreen::QuestionList => {
@@ -494,6 +1245,9 @@ fn build_footer_paragraph(app: &AppState) -> Paragraph<'static> {
             CurrentScreen::DifficultyFilter => {
                 Span::styled(" Difficulty Filter ", Style::default().bg(LIGHT_ORANGE).fg(DARK_TEXT_COLOR))
             }
+            CurrentScreen::SourceFilter => {
+                Span::styled(" Source Filter ", Style::default().bg(LIGHT_ORANGE).fg(DARK_TEXT_COLOR))
+            }
         },
         // Divider
         Span::styled(" | ", Style::default().fg(TITLE_TEXT_COLOR)),
@@ -507,29 +1261,37 @@ fn build_footer_paragraph(app: &AppState) -> Paragraph<'static> {
                 "Type to search | Backspace: Delete | ↑/↓: Navigate ",
                 Style::default().fg(HIGHLIGHT_COLOR),
             ),
-            CurrentScreen::DifficultyFilter => Span::styled(
+            CurrentScreen::DifficultyFilter | CurrentScreen::SourceFilter => Span::styled(
                 "↑/↓: Navigate",
                 Style::default().fg(HIGHLIGHT_COLOR),
             ),
         },
 
         Span::styled(" | ", Style::default().fg(TITLE_TEXT_COLOR)),
-    ];
+    ]
+    .into_iter()
+    .chain(error_spans(app))
+    .chain(sync_status_spans(app))
+    .collect();
 
     // Key hints (right side of footer or below navigation)
     let current_keys_hint: Span = match app.current_screen {
         CurrentScreen::QuestionList => Span::styled(
-            "Ctrl + T to filter by topic / Ctrl + D to filter by difficulty / Ctrl + C: Quit",
+            "Ctrl + T to filter by topic / Ctrl + D to filter by difficulty / Ctrl + P to filter by source / Ctrl + F: Fuzzy/Full-text / Ctrl + O: Sort / Ctrl + B: Bookmark / Ctrl + S: Solved / Ctrl + R: Sync / Ctrl + C: Quit",
             Style::default().fg(LIGHT_ORANGE),
         ),
         CurrentScreen::TopicList => Span::styled(
-            "Enter to select the topic / Esc to cancel",
+            "Tab to toggle a topic / Ctrl + A: any/all / Enter to apply / Esc to cancel",
             Style::default().fg(LIGHT_ORANGE),
         ),
         CurrentScreen::DifficultyFilter => Span::styled(
             "Enter to apply difficulty level / Esc to cancel",
             Style::default().fg(LIGHT_ORANGE),
         ),
+        CurrentScreen::SourceFilter => Span::styled(
+            "Enter to apply source / Esc to cancel",
+            Style::default().fg(LIGHT_ORANGE),
+        ),
     };
 
     // Final footer paragraph
@@ -547,3 +1309,54 @@ fn build_footer_paragraph(app: &AppState) -> Paragraph<'static> {
     )
     .style(Style::default().bg(POPUP_BACKGROUND_COLOR))
 }
+
+/// Footer segment with the error of the last failed action, if any.
+fn error_spans(app: &AppState) -> Vec<Span<'static>> {
+    match &app.error_message {
+        Some(message) => vec![
+            Span::styled(message.clone(), Style::default().fg(HARD_COLOR)),
+            Span::styled(" | ", Style::default().fg(TITLE_TEXT_COLOR)),
+        ],
+        None => Vec::new(),
+    }
+}
+
+/// Footer segment describing the sync started with Ctrl + R and when the
+/// replica was last synced.
+fn sync_status_spans(app: &AppState) -> Vec<Span<'static>> {
+    let divider = || Span::styled(" | ", Style::default().fg(TITLE_TEXT_COLOR));
+    let mut spans = Vec::new();
+
+    match &app.sync_status {
+        SyncStatus::Idle => {}
+        SyncStatus::Running { stage, started_at } => spans.push(Span::styled(
+            format!("⟳ {stage}… {}s", started_at.elapsed().as_secs()),
+            Style::default().fg(TITLE_TEXT_COLOR),
+        )),
+        SyncStatus::Finished(report) => spans.push(Span::styled(
+            format!(
+                "Synced {} frames: +{} ~{} -{}",
+                report.frames_synced,
+                report.diff.added.len(),
+                report.diff.modified.len(),
+                report.diff.removed.len()
+            ),
+            Style::default().fg(EASY_COLOR),
+        )),
+        SyncStatus::Failed(message) => {
+            spans.push(Span::styled(message.clone(), Style::default().fg(HARD_COLOR)))
+        }
+    }
+    if !spans.is_empty() {
+        spans.push(divider());
+    }
+
+    if let Some(last_synced_at) = app.last_synced_at {
+        spans.push(Span::styled(
+            format!("Last sync: {}", last_synced_at.format("%Y-%m-%d %H:%M")),
+            Style::default().fg(TEXT_COLOR),
+        ));
+        spans.push(divider());
+    }
+    spans
+}
@@ -1,4 +1,6 @@
+pub mod event;
 pub mod list;
+pub mod sync;
 
 use ratatui::{
     backend::CrosstermBackend,
@@ -0,0 +1,75 @@
+use std::time::Instant;
+
+use chrono::Local;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::db::search_index::{get_search_contents, rebuild_search_index};
+use crate::db::sync_zuko_db;
+use crate::db::zuko_cli::{get_all_question_summaries, get_all_topics};
+use crate::types::SyncStatus;
+use crate::ui::event::AppEvent;
+
+/// Syncs the replica in the background and reloads the questions and topics
+/// once it's done, reporting each step through `status`.
+pub fn start_sync(status: &mut SyncStatus, events: &UnboundedSender<AppEvent>) {
+    if status.is_running() {
+        return;
+    }
+    *status = running("Replicating");
+
+    let events = events.clone();
+    tokio::spawn(async move {
+        let report = match sync_zuko_db().await {
+            Ok(report) => report,
+            Err(e) => return fail(&events, format!("Sync failed: {e}")),
+        };
+
+        set_stage(&events, "Rebuilding search index");
+        if let Err(e) = rebuild_search_index().await {
+            return fail(&events, format!("Failed to rebuild the search index: {e}"));
+        }
+
+        set_stage(&events, "Reloading questions");
+        let questions = match get_all_question_summaries().await {
+            Ok(questions) => questions,
+            Err(e) => return fail(&events, format!("Failed to reload questions: {e}")),
+        };
+        let topics = match get_all_topics().await {
+            Ok(topics) => topics,
+            Err(e) => return fail(&events, format!("Failed to reload topics: {e}")),
+        };
+        let contents = match get_search_contents().await {
+            Ok(contents) => contents,
+            Err(e) => return fail(&events, format!("Failed to reload statements: {e}")),
+        };
+
+        let _ = events.send(AppEvent::Update(Box::new(move |app| {
+            app.set_questions(questions);
+            app.set_search_contents(&contents);
+            app.set_topics(topics);
+            app.sync_status = SyncStatus::Finished(report);
+            app.last_synced_at = Some(Local::now());
+        })));
+    });
+}
+
+fn running(stage: &'static str) -> SyncStatus {
+    SyncStatus::Running { stage, started_at: Instant::now() }
+}
+
+fn set_stage(events: &UnboundedSender<AppEvent>, stage: &'static str) {
+    let _ = events.send(AppEvent::Update(Box::new(move |app| {
+        // keep the start time so the elapsed time covers the whole sync
+        if let SyncStatus::Running { started_at, .. } = app.sync_status {
+            app.sync_status = SyncStatus::Running { stage, started_at };
+        } else {
+            app.sync_status = running(stage);
+        }
+    })));
+}
+
+fn fail(events: &UnboundedSender<AppEvent>, message: String) {
+    let _ = events.send(AppEvent::Update(Box::new(move |app| {
+        app.sync_status = SyncStatus::Failed(message);
+    })));
+}
@@ -0,0 +1,70 @@
+use crate::types::Limit;
+
+/// The slowest complexity that usually passes for an input size of at most the
+/// first number, assuming around 10^8 simple operations in the time limit.
+const COMPLEXITIES: [(i64, &str); 6] = [
+    (12, "O(n!)"),
+    (25, "O(2ⁿ)"),
+    (500, "O(n³)"),
+    (10_000, "O(n²)"),
+    (1_000_000, "O(n log n)"),
+    (100_000_000, "O(n)"),
+];
+
+/// The complexity a solution should aim for given the input sizes in `limits`,
+/// e.g. `O(n log n)` for `n <= 10^5`.
+pub fn suggested_complexity(limits: &[Limit]) -> Option<&'static str> {
+    let size = limits
+        .iter()
+        .filter(|limit| is_size(&limit.variable))
+        .filter_map(|limit| limit.upper.as_ref()?.value)
+        .max()?;
+    Some(
+        COMPLEXITIES
+            .iter()
+            .find(|(max_size, _)| size <= *max_size)
+            .map_or("O(log n)", |(_, complexity)| complexity),
+    )
+}
+
+/// Whether `variable` is the size of the input rather than a value in it, like
+/// `nums.length` or `n` but not `nums[i]` or `k`.
+fn is_size(variable: &str) -> bool {
+    let variable = variable.to_lowercase();
+    matches!(variable.as_str(), "n" | "m")
+        || variable.ends_with("length")
+        || variable.ends_with("size")
+        || variable.contains("number of")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Bound;
+
+    fn limit(variable: &str, upper: i64) -> Limit {
+        Limit {
+            variable: variable.to_string(),
+            lower: None,
+            upper: Some(Bound { text: upper.to_string(), value: Some(upper), inclusive: true }),
+        }
+    }
+
+    #[test]
+    fn picks_the_complexity_for_the_largest_size() {
+        assert_eq!(suggested_complexity(&[limit("n", 20)]), Some("O(2ⁿ)"));
+        assert_eq!(suggested_complexity(&[limit("m", 100), limit("n", 100)]), Some("O(n³)"));
+        assert_eq!(
+            suggested_complexity(&[limit("nums.length", 100_000), limit("nums[i]", 1_000_000_000)]),
+            Some("O(n log n)")
+        );
+        assert_eq!(suggested_complexity(&[limit("number of nodes in the tree", 10_000)]), Some("O(n²)"));
+        assert_eq!(suggested_complexity(&[limit("n", 1_000_000_000)]), Some("O(log n)"));
+    }
+
+    #[test]
+    fn needs_a_size() {
+        assert_eq!(suggested_complexity(&[]), None);
+        assert_eq!(suggested_complexity(&[limit("k", 100)]), None);
+    }
+}
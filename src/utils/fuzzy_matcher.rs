@@ -1,28 +1,367 @@
-use crate::types::{Question, Topic};
+use crate::types::{Difficulty, Progress, ProgressStatus, QuestionSource, QuestionSummary, Topic};
 
 use nucleo_matcher::{
-    pattern::{Pattern, CaseMatching, Normalization},
-    Matcher, Config,
+    pattern::{Atom, AtomKind, Pattern, CaseMatching, Normalization},
+    Matcher, Config, Utf32Str,
 };
+use unicode_segmentation::UnicodeSegmentation;
 
-pub fn search_questions(questions: &[Question], query: &str) -> Vec<usize> {
-    let mut matcher = Matcher::new(Config::DEFAULT);
-    let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+/// A structured filter typed into the search box, e.g. `t:graph` or `is:solved`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueryFilter {
+    /// `t:<slug>` / `topic:<slug>`: any topic slug contains the value
+    Topic(String),
+    /// `d:<level>` / `difficulty:<level>`, prefixes like `d:h` are accepted
+    Difficulty(Difficulty),
+    /// `src:<source>` / `source:<source>`: the source, e.g. `imported` or
+    /// `pack:graph-drills`, starts with the value; `pack:<name>` is short for
+    /// `src:pack:<name>`
+    Source(String),
+    /// `is:solved`
+    Solved,
+    /// `is:attempted`
+    Attempted,
+    /// `is:bookmarked` / `is:bm`
+    Bookmarked,
+}
 
-    // Build a vector of titles with their indices
-    let title_refs: Vec<(usize, &str)> = questions
-        .iter()
-        .enumerate()
-        .map(|(i, q)| (i, q.title.as_str()))
-        .collect();
-    
-    let matches = pattern.match_list(title_refs.iter().map(|(_, title)| *title), &mut matcher);
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryClause {
+    pub filter: QueryFilter,
+    pub negated: bool,
+}
+
+/// Free text matched against the title, the slug and the plain-text content.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryTerm {
+    pub text: String,
+    /// Quoted phrases are matched as substrings instead of fuzzily.
+    pub phrase: bool,
+    pub negated: bool,
+}
+
+/// Parsed form of the search box, e.g. `t:graph d:hard "shortest path" !solved`.
+///
+/// Every filter and term may be negated with a leading `!`; the flag names
+/// (`solved`, `attempted`, `bookmarked`) can be negated without `is:`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchQuery {
+    pub clauses: Vec<QueryClause>,
+    pub terms: Vec<QueryTerm>,
+}
+
+impl SearchQuery {
+    pub fn parse(input: &str) -> Self {
+        let mut query = SearchQuery::default();
+
+        for token in tokenize(input) {
+            let (negated, token) = match token.strip_prefix('!') {
+                Some(rest) => (true, rest),
+                None => (false, token.as_str()),
+            };
+            if token.is_empty() {
+                continue;
+            }
+
+            if let Some(phrase) = token.strip_prefix('"') {
+                let text = phrase.trim_end_matches('"').trim();
+                if !text.is_empty() {
+                    query.terms.push(QueryTerm { text: text.to_string(), phrase: true, negated });
+                }
+                continue;
+            }
+
+            if let Some((key, value)) = token.split_once(':')
+                && is_filter_key(key)
+            {
+                // Incomplete filters such as `d:` are ignored while the user is still typing.
+                if let Some(filter) = parse_filter(key, value) {
+                    query.clauses.push(QueryClause { filter, negated });
+                }
+                continue;
+            }
+
+            if negated && let Some(filter) = parse_flag(token) {
+                query.clauses.push(QueryClause { filter, negated });
+                continue;
+            }
+
+            query.terms.push(QueryTerm {
+                text: token.replace('"', ""),
+                phrase: false,
+                negated,
+            });
+        }
+
+        query
+    }
 
-    matches
+    fn matches_filters(&self, item: &SearchItem, progress: Option<&Progress>) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.filter.matches(item, progress) != clause.negated)
+    }
+
+    /// Whether every question matching `self` is guaranteed to also match `previous`,
+    /// so the previous results can be used as the candidate set.
+    pub fn narrows(&self, previous: &SearchQuery) -> bool {
+        let clauses_narrow = previous.clauses.iter().all(|old| {
+            self.clauses.iter().any(|new| {
+                new == old
+                    || match (&old.filter, &new.filter) {
+                        (QueryFilter::Topic(o), QueryFilter::Topic(n)) => {
+                            !old.negated && !new.negated && n.contains(o.as_str())
+                        }
+                        (QueryFilter::Source(o), QueryFilter::Source(n)) => {
+                            !old.negated && !new.negated && n.starts_with(o.as_str())
+                        }
+                        _ => false,
+                    }
+            })
+        });
+        // Terms are paired by position: each old term must have been extended in place.
+        // Negated terms exclude more as they get shorter, so they must stay untouched.
+        let terms_narrow = previous.terms.len() <= self.terms.len()
+            && previous.terms.iter().zip(&self.terms).all(|(old, new)| {
+                old == new
+                    || (!old.negated
+                        && !new.negated
+                        && old.phrase == new.phrase
+                        && new.text.to_lowercase().contains(&old.text.to_lowercase()))
+            });
+        clauses_narrow && terms_narrow
+    }
+}
+
+impl QueryFilter {
+    fn matches(&self, item: &SearchItem, progress: Option<&Progress>) -> bool {
+        match self {
+            QueryFilter::Topic(slug) => item.topic_slugs.iter().any(|t| t.contains(slug.as_str())),
+            QueryFilter::Difficulty(difficulty) => item
+                .difficulty
+                .as_deref()
+                .is_some_and(|d| d.eq_ignore_ascii_case(difficulty.to_str())),
+            QueryFilter::Source(prefix) => item.source.to_string().starts_with(prefix.as_str()),
+            QueryFilter::Solved => progress.is_some_and(|p| p.status == ProgressStatus::Solved),
+            QueryFilter::Attempted => progress.is_some_and(|p| p.status == ProgressStatus::Attempted),
+            QueryFilter::Bookmarked => progress.is_some_and(|p| p.bookmarked),
+        }
+    }
+}
+
+/// Splits on whitespace, keeping double-quoted sections (and their quotes) inside one token.
+fn tokenize(input: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+
+    for c in input.chars() {
+        match c {
+            '"' => {
+                in_quotes = !in_quotes;
+                current.push(c);
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if !current.is_empty() {
+                    tokens.push(std::mem::take(&mut current));
+                }
+            }
+            _ => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        tokens.push(current);
+    }
+    tokens
+}
+
+fn is_filter_key(key: &str) -> bool {
+    matches!(
+        key.to_lowercase().as_str(),
+        "t" | "topic" | "d" | "diff" | "difficulty" | "src" | "source" | "pack" | "is"
+    )
+}
+
+fn parse_filter(key: &str, value: &str) -> Option<QueryFilter> {
+    let value = value.trim_matches('"').to_lowercase();
+    if value.is_empty() {
+        return None;
+    }
+    match key.to_lowercase().as_str() {
+        "t" | "topic" => Some(QueryFilter::Topic(value)),
+        "d" | "diff" | "difficulty" => parse_difficulty(&value).map(QueryFilter::Difficulty),
+        "src" | "source" => Some(QueryFilter::Source(value)),
+        "pack" => Some(QueryFilter::Source(format!("pack:{}", value))),
+        "is" => parse_flag(&value),
+        _ => None,
+    }
+}
+
+fn parse_difficulty(value: &str) -> Option<Difficulty> {
+    [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard]
         .into_iter()
-        .filter_map(|(title, _score)| {
-            title_refs.iter().find(|(_, t)| *t == title).map(|(i, _)| *i)
-        })
+        .find(|d| d.to_str().to_lowercase().starts_with(value))
+}
+
+fn parse_flag(value: &str) -> Option<QueryFilter> {
+    match value.to_lowercase().as_str() {
+        "solved" => Some(QueryFilter::Solved),
+        "attempted" => Some(QueryFilter::Attempted),
+        "bookmarked" | "bookmark" | "bm" => Some(QueryFilter::Bookmarked),
+        _ => None,
+    }
+}
+
+/// The searchable projection of a [`QuestionSummary`], built once when questions are loaded.
+#[derive(Debug, Clone)]
+pub struct SearchItem {
+    pub title: String,
+    /// `title` lowercased once, for the substring checks of every keystroke.
+    pub lowercase_title: String,
+    pub title_slug: String,
+    /// Lowercased topic slugs.
+    pub topic_slugs: Vec<String>,
+    pub difficulty: Option<String>,
+    pub source: QuestionSource,
+    /// Lowercased, HTML-stripped `content`; empty until the statements are loaded,
+    /// see `AppState::set_search_contents`.
+    pub content: String,
+}
+
+impl SearchItem {
+    pub fn from_question(question: &QuestionSummary) -> Self {
+        SearchItem {
+            title: question.title.clone(),
+            lowercase_title: question.title.to_lowercase(),
+            title_slug: question.title_slug.clone(),
+            topic_slugs: question
+                .topic
+                .iter()
+                .flatten()
+                .map(|t| t.slug.to_lowercase())
+                .collect(),
+            difficulty: question.difficulty.clone(),
+            source: question.source.clone(),
+            content: String::new(),
+        }
+    }
+}
+
+/// A ranked search hit.
+#[derive(Debug, Clone)]
+pub struct QuestionMatch {
+    /// Index into the searched items.
+    pub index: usize,
+    pub score: u32,
+    /// Sorted char positions in the title matched by the free-text terms.
+    pub title_indices: Vec<u32>,
+}
+
+/// Matches [`SearchItem`]s one at a time against a parsed [`SearchQuery`], so callers
+/// can restrict the candidates and stop early.
+pub struct QueryMatcher {
+    query: SearchQuery,
+    /// Each term with its nucleo atom and lowercased text for substring checks.
+    atoms: Vec<(Atom, String)>,
+    matcher: Matcher,
+    buf: Vec<char>,
+}
+
+impl QueryMatcher {
+    pub fn new(query: SearchQuery) -> Self {
+        let atoms = query
+            .terms
+            .iter()
+            .map(|term| {
+                let kind = if term.phrase { AtomKind::Substring } else { AtomKind::Fuzzy };
+                let atom = Atom::new(&term.text, CaseMatching::Ignore, Normalization::Smart, kind, false);
+                (atom, term.text.to_lowercase())
+            })
+            .collect();
+
+        QueryMatcher {
+            query,
+            atoms,
+            matcher: Matcher::new(Config::DEFAULT),
+            buf: Vec::new(),
+        }
+    }
+
+    pub fn query(&self) -> &SearchQuery {
+        &self.query
+    }
+
+    /// Scores `item` with the filters and terms described on [`SearchQuery`];
+    /// `progress` is the user's progress on that question.
+    pub fn match_item(
+        &mut self,
+        index: usize,
+        item: &SearchItem,
+        progress: Option<&Progress>,
+    ) -> Option<QuestionMatch> {
+        if !self.query.matches_filters(item, progress) {
+            return None;
+        }
+
+        let mut score = 0;
+        let mut title_indices = Vec::new();
+        for (term, (atom, lowered)) in self.query.terms.iter().zip(&self.atoms) {
+            let in_text = item.lowercase_title.contains(lowered.as_str())
+                || item.title_slug.contains(lowered.as_str())
+                || item.content.contains(lowered.as_str());
+            if term.negated {
+                if in_text {
+                    return None;
+                }
+                continue;
+            }
+            // Title matches rank highest, then slug matches, then hits in the statement.
+            score += atom
+                .indices(Utf32Str::new(&item.title, &mut self.buf), &mut self.matcher, &mut title_indices)
+                .map(|s| s as u32 * 2)
+                .or_else(|| {
+                    atom.score(Utf32Str::new(&item.title_slug, &mut self.buf), &mut self.matcher)
+                        .map(u32::from)
+                })
+                .or_else(|| in_text.then_some(1))?;
+        }
+        let mut title_indices = char_indices(&item.title, &title_indices);
+        // several atoms may highlight the same chars
+        title_indices.sort_unstable();
+        title_indices.dedup();
+        Some(QuestionMatch { index, score, title_indices })
+    }
+}
+
+/// Turns the positions nucleo matched in `text` into char positions. Like
+/// [`Utf32Str::new`], nucleo counts bytes when the first char of every grapheme
+/// is ASCII, and graphemes otherwise; a matched grapheme covers all its chars.
+fn char_indices(text: &str, matched: &[u32]) -> Vec<u32> {
+    if text.is_ascii() {
+        return matched.to_vec();
+    }
+    let mut graphemes = Vec::new();
+    let mut chars = 0;
+    for (byte, grapheme) in text.grapheme_indices(true) {
+        let len = grapheme.chars().count() as u32;
+        graphemes.push((byte, grapheme, chars..chars + len));
+        chars += len;
+    }
+
+    let by_bytes = graphemes.iter().all(|(_, grapheme, _)| grapheme.starts_with(|c: char| c.is_ascii()));
+    if by_bytes {
+        // ASCII-led graphemes start with a one-byte char, so a matched byte is
+        // always the start of its grapheme
+        return matched
+            .iter()
+            .filter_map(|&i| graphemes.iter().find(|(byte, _, _)| *byte == i as usize))
+            .flat_map(|(_, _, chars)| chars.clone())
+            .collect();
+    }
+    matched
+        .iter()
+        .filter_map(|&i| graphemes.get(i as usize))
+        .flat_map(|(_, _, chars)| chars.clone())
         .collect()
 }
 
@@ -30,21 +369,132 @@ pub fn search_questions(questions: &[Question], query: &str) -> Vec<usize> {
 pub fn search_topics(topics: &[Topic], query: &str) -> Vec<usize> {
     let mut matcher = Matcher::new(Config::DEFAULT);
     let pattern = Pattern::parse(query, CaseMatching::Ignore, Normalization::Smart);
+    let mut buf = Vec::new();
 
-    // Build a vector of topics with their indices
-    let topic_refs: Vec<(usize, &str)> = topics
+    // Score by position so topics sharing a name keep their own index
+    let mut scored: Vec<(usize, u32)> = topics
         .iter()
         .enumerate()
-        .map(|(i, t)| (i, t.name.as_str()))
+        .filter_map(|(i, t)| {
+            pattern
+                .score(Utf32Str::new(&t.name, &mut buf), &mut matcher)
+                .map(|score| (i, score))
+        })
         .collect();
 
-    let matches = pattern.match_list(topic_refs.iter().map(|(_, topic)| *topic), &mut matcher);
-
-    matches
-        .into_iter()
-        .filter_map(|(topic, _score)| {
-            topic_refs.iter().find(|(_, t)| *t == topic).map(|(i, _)| *i)
-        })
-        .collect()
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(i, _)| i).collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn title_match(title: &str, query: &str) -> Vec<u32> {
+        let item = SearchItem {
+            title: title.to_string(),
+            lowercase_title: title.to_lowercase(),
+            title_slug: String::new(),
+            topic_slugs: Vec::new(),
+            difficulty: None,
+            source: QuestionSource::Synced,
+            content: String::new(),
+        };
+        QueryMatcher::new(SearchQuery::parse(query))
+            .match_item(0, &item, None)
+            .map(|m| m.title_indices)
+            .unwrap_or_default()
+    }
+
+    fn clause(filter: QueryFilter, negated: bool) -> QueryClause {
+        QueryClause { filter, negated }
+    }
+
+    fn term(text: &str, phrase: bool, negated: bool) -> QueryTerm {
+        QueryTerm { text: text.to_string(), phrase, negated }
+    }
+
+    #[test]
+    fn parses_filters_and_terms() {
+        let query = SearchQuery::parse(r#"t:graph d:hard "shortest path" !solved dijkstra !heap"#);
+        assert_eq!(
+            query.clauses,
+            [
+                clause(QueryFilter::Topic("graph".to_string()), false),
+                clause(QueryFilter::Difficulty(Difficulty::Hard), false),
+                clause(QueryFilter::Solved, true),
+            ]
+        );
+        assert_eq!(
+            query.terms,
+            [
+                term("shortest path", true, false),
+                term("dijkstra", false, false),
+                term("heap", false, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_filter_aliases() {
+        let query = SearchQuery::parse("topic:Array diff:m is:bm !is:attempted src:imported pack:drills");
+        assert_eq!(
+            query.clauses,
+            [
+                clause(QueryFilter::Topic("array".to_string()), false),
+                clause(QueryFilter::Difficulty(Difficulty::Medium), false),
+                clause(QueryFilter::Bookmarked, false),
+                clause(QueryFilter::Attempted, true),
+                clause(QueryFilter::Source("imported".to_string()), false),
+                clause(QueryFilter::Source("pack:drills".to_string()), false),
+            ]
+        );
+        assert!(query.terms.is_empty());
+    }
+
+    #[test]
+    fn ignores_incomplete_input() {
+        let query = SearchQuery::parse(r#"d: t: ! "" is:unknown"#);
+        assert!(query.clauses.is_empty());
+        assert!(query.terms.is_empty());
+
+        // an unknown key is free text, and `solved` without `!` is a word
+        let query = SearchQuery::parse("foo:bar solved");
+        assert!(query.clauses.is_empty());
+        assert_eq!(query.terms, [term("foo:bar", false, false), term("solved", false, false)]);
+    }
+
+    #[test]
+    fn extending_the_query_narrows_it() {
+        let narrows = |new: &str, old: &str| SearchQuery::parse(new).narrows(&SearchQuery::parse(old));
+
+        assert!(narrows("two", "tw"));
+        assert!(narrows("two sum", "two"));
+        assert!(narrows("t:graph two", "t:gr"));
+        assert!(narrows("t:graph d:hard", "t:graph"));
+        assert!(narrows("pack:drills", "src:pack"));
+        assert!(narrows(r#""shortest path""#, r#""shortest"#));
+    }
+
+    #[test]
+    fn other_changes_do_not_narrow() {
+        let narrows = |new: &str, old: &str| SearchQuery::parse(new).narrows(&SearchQuery::parse(old));
+
+        assert!(!narrows("tw", "two"));
+        assert!(!narrows("two", "two sum"));
+        assert!(!narrows("t:graph", "t:graph d:hard"));
+        // a shorter negated term excludes more, a longer one less
+        assert!(!narrows("!heap", "!he"));
+        assert!(!narrows("!t:graph", "!t:gr"));
+        assert!(!narrows(r#""two""#, "two"));
+    }
+
+    #[test]
+    fn highlights_chars_of_non_ascii_titles() {
+        assert_eq!(title_match("Two Sum", "sum"), [4, 5, 6]);
+        // "é" is two chars here, so the grapheme and char positions differ
+        assert_eq!(title_match("Cafe\u{301} Sum", "sum"), [6, 7, 8]);
+        assert_eq!(title_match("Cafe\u{301} Sum", "cafe"), [0, 1, 2, 3, 4]);
+        assert_eq!(title_match("Ü e\u{301} Sum", "sum"), [5, 6, 7]);
+    }
+}
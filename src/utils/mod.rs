@@ -1,5 +1,13 @@
 pub mod fuzzy_matcher;
+pub mod search_worker;
 pub mod parse_html;
+pub mod preview_cache;
+pub mod problem_export;
+pub mod problem_import;
+pub mod problem_pack;
+pub mod question_details;
+pub mod question_index;
 pub mod bootstrap_solution;
+pub mod complexity;
 pub mod serde_json_string;
 pub mod ui;
\ No newline at end of file
@@ -0,0 +1,342 @@
+use super::{flatten, plain_text, Block};
+use crate::types::{Bound, Limit};
+
+/// Pulls the limits out of the blocks of a statement's `Constraints:` list, e.g. `1 <= n <= 10^5`.
+/// Items that aren't comparisons, like "s consists of lowercase letters", are left out.
+pub fn limits_in(blocks: Vec<Block>) -> Vec<Limit> {
+    let Some(start) = blocks.iter().position(|block| {
+        matches!(block, Block::Text(content) | Block::Paragraph(content)
+            if content.iter().map(plain_text).collect::<String>().trim().starts_with("Constraints"))
+    }) else {
+        return Vec::new();
+    };
+    // the list comes right after the heading, before any other text
+    let Some(Block::List { items, .. }) = blocks
+        .into_iter()
+        .skip(start + 1)
+        .find(|block| !matches!(block, Block::Paragraph(content) if content.is_empty()))
+    else {
+        return Vec::new();
+    };
+
+    items
+        .into_iter()
+        .flat_map(|item| {
+            let text: String = flatten(item).iter().map(plain_text).collect();
+            parse_limit(&text)
+        })
+        .collect()
+}
+
+/// The limits of one constraint. `1 <= m, n <= 100` limits two variables.
+fn parse_limit(text: &str) -> Vec<Limit> {
+    let text = text.trim().trim_end_matches('.').replace('≤', "<=").replace('≥', ">=");
+    if let Some(limits) = parse_range(&text) {
+        return limits;
+    }
+
+    let (parts, operators) = split_comparisons(&text);
+    let (variables, lower, upper) = match (parts.as_slice(), operators.as_slice()) {
+        ([low, variable, high], [first, second]) if first.less() && second.less() => {
+            (*variable, Some(bound(low, *first)), Some(bound(high, *second)))
+        }
+        ([high, variable, low], [first, second]) if first.greater() && second.greater() => {
+            (*variable, Some(bound(low, *second)), Some(bound(high, *first)))
+        }
+        ([left, right], [operator]) if *operator != Comparison::Equal => {
+            // the variable is the side that isn't a number, e.g. `0 <= k` or `k <= n`
+            let (variable, limit, operator) = if evaluate(left).is_some() {
+                (*right, *left, operator.flipped())
+            } else {
+                (*left, *right, *operator)
+            };
+            if evaluate(variable).is_some() {
+                return Vec::new();
+            }
+            let limit = bound(limit, operator);
+            if operator.less() { (variable, None, Some(limit)) } else { (variable, Some(limit), None) }
+        }
+        _ => return Vec::new(),
+    };
+    // not a comparison after all, e.g. `s[i] is '<' or '>'`
+    if !variables.chars().all(|c| c.is_alphanumeric() || "_.[](), ".contains(c)) {
+        return Vec::new();
+    }
+
+    variables
+        .split(',')
+        .map(str::trim)
+        .filter(|variable| !variable.is_empty())
+        .map(|variable| Limit {
+            variable: variable.to_string(),
+            lower: lower.clone(),
+            upper: upper.clone(),
+        })
+        .collect()
+}
+
+/// Constraints written as `The number of nodes in the tree is in the range [0, 10^4]`.
+fn parse_range(text: &str) -> Option<Vec<Limit>> {
+    let (variable, range) = text.split_once(" in the range ")?;
+    let variable = variable.trim_end_matches(" is").trim_end_matches(" are");
+    let variable = variable.strip_prefix("The ").unwrap_or(variable);
+    let (low, high) = range.trim().strip_prefix('[')?.strip_suffix(']')?.split_once(',')?;
+    Some(vec![Limit {
+        variable: variable.trim().to_string(),
+        lower: Some(bound(low, Comparison::LessOrEqual)),
+        upper: Some(bound(high, Comparison::LessOrEqual)),
+    }])
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Comparison {
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+    Equal,
+}
+
+impl Comparison {
+    fn less(self) -> bool {
+        matches!(self, Comparison::Less | Comparison::LessOrEqual)
+    }
+
+    fn greater(self) -> bool {
+        matches!(self, Comparison::Greater | Comparison::GreaterOrEqual)
+    }
+
+    /// The comparison with its sides swapped.
+    fn flipped(self) -> Self {
+        match self {
+            Comparison::Less => Comparison::Greater,
+            Comparison::LessOrEqual => Comparison::GreaterOrEqual,
+            Comparison::Greater => Comparison::Less,
+            Comparison::GreaterOrEqual => Comparison::LessOrEqual,
+            Comparison::Equal => Comparison::Equal,
+        }
+    }
+}
+
+/// Splits `text` around its comparison operators.
+fn split_comparisons(text: &str) -> (Vec<&str>, Vec<Comparison>) {
+    const OPERATORS: [(&str, Comparison); 5] = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("==", Comparison::Equal),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+    let mut parts = Vec::new();
+    let mut operators = Vec::new();
+    let mut part_start = 0;
+    let mut i = 0;
+    while i < text.len() {
+        match OPERATORS.iter().find(|(symbol, _)| text[i..].starts_with(symbol)) {
+            Some((symbol, operator)) => {
+                parts.push(text[part_start..i].trim());
+                operators.push(*operator);
+                i += symbol.len();
+                part_start = i;
+            }
+            None => i += text[i..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    parts.push(text[part_start..].trim());
+    (parts, operators)
+}
+
+/// A bound on the side of `operator` away from the variable; `<` and `>` exclude it.
+fn bound(text: &str, operator: Comparison) -> Bound {
+    let text = text.trim();
+    Bound {
+        text: text.to_string(),
+        value: evaluate(text),
+        inclusive: !matches!(operator, Comparison::Less | Comparison::Greater),
+    }
+}
+
+/// The value of a bound like `10^5`, `2 * 10^5`, `-2^31` or `10^9 + 7`.
+fn evaluate(text: &str) -> Option<i64> {
+    let chars: Vec<char> = text
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != ',')
+        .map(|c| if c == '−' { '-' } else { c })
+        .collect();
+    let mut parser = Evaluator { chars: &chars, position: 0 };
+    let value = parser.sum()?;
+    if parser.position != chars.len() {
+        return None;
+    }
+    i64::try_from(value).ok()
+}
+
+struct Evaluator<'a> {
+    chars: &'a [char],
+    position: usize,
+}
+
+impl Evaluator<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn sum(&mut self) -> Option<i128> {
+        let mut value = self.product()?;
+        while let Some(c @ ('+' | '-')) = self.peek() {
+            self.position += 1;
+            let term = self.product()?;
+            value = if c == '+' { value.checked_add(term)? } else { value.checked_sub(term)? };
+        }
+        Some(value)
+    }
+
+    fn product(&mut self) -> Option<i128> {
+        let mut value = self.unary()?;
+        while let Some('*' | '×' | '·') = self.peek() {
+            self.position += 1;
+            value = value.checked_mul(self.unary()?)?;
+        }
+        Some(value)
+    }
+
+    fn unary(&mut self) -> Option<i128> {
+        if self.peek() == Some('-') {
+            self.position += 1;
+            return self.unary()?.checked_neg();
+        }
+        self.power()
+    }
+
+    fn power(&mut self) -> Option<i128> {
+        let base = self.atom()?;
+        if self.peek() != Some('^') {
+            return Some(base);
+        }
+        self.position += 1;
+        let exponent = u32::try_from(self.unary()?).ok()?;
+        base.checked_pow(exponent)
+    }
+
+    fn atom(&mut self) -> Option<i128> {
+        if self.peek() == Some('(') {
+            self.position += 1;
+            let value = self.sum()?;
+            if self.peek() != Some(')') {
+                return None;
+            }
+            self.position += 1;
+            return Some(value);
+        }
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.chars[start..self.position].iter().collect::<String>().parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_html::parse_statement;
+
+    fn inclusive(text: &str, value: i64) -> Option<Bound> {
+        Some(Bound { text: text.to_string(), value: Some(value), inclusive: true })
+    }
+
+    #[test]
+    fn evaluates_bounds() {
+        assert_eq!(evaluate("10^5"), Some(100_000));
+        assert_eq!(evaluate("2 * 10^5"), Some(200_000));
+        assert_eq!(evaluate("-2^31"), Some(-2_147_483_648));
+        assert_eq!(evaluate("2^31 - 1"), Some(2_147_483_647));
+        assert_eq!(evaluate("10^9 + 7"), Some(1_000_000_007));
+        assert_eq!(evaluate("−10^4"), Some(-10_000));
+        assert_eq!(evaluate("100,000"), Some(100_000));
+        assert_eq!(evaluate("nums.length"), None);
+        assert_eq!(evaluate("10^100"), None);
+    }
+
+    #[test]
+    fn parses_a_chain() {
+        assert_eq!(
+            parse_limit("1 <= nums.length <= 10^4"),
+            vec![Limit {
+                variable: "nums.length".to_string(),
+                lower: inclusive("1", 1),
+                upper: inclusive("10^4", 10_000),
+            }]
+        );
+        assert_eq!(
+            parse_limit("-2^31 ≤ nums[i] ≤ 2^31 - 1."),
+            vec![Limit {
+                variable: "nums[i]".to_string(),
+                lower: inclusive("-2^31", -2_147_483_648),
+                upper: inclusive("2^31 - 1", 2_147_483_647),
+            }]
+        );
+    }
+
+    #[test]
+    fn splits_several_variables() {
+        let limits = parse_limit("1 <= m, n <= 100");
+        let variables: Vec<&str> = limits.iter().map(|l| l.variable.as_str()).collect();
+        assert_eq!(variables, ["m", "n"]);
+        assert!(limits.iter().all(|l| l.upper == inclusive("100", 100)));
+    }
+
+    #[test]
+    fn parses_one_sided_comparisons() {
+        assert_eq!(
+            parse_limit("0 < k"),
+            vec![Limit {
+                variable: "k".to_string(),
+                lower: Some(Bound { text: "0".to_string(), value: Some(0), inclusive: false }),
+                upper: None,
+            }]
+        );
+        assert_eq!(
+            parse_limit("k < n"),
+            vec![Limit {
+                variable: "k".to_string(),
+                lower: None,
+                upper: Some(Bound { text: "n".to_string(), value: None, inclusive: false }),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(
+            parse_limit("The number of nodes in the tree is in the range [0, 10^4]."),
+            vec![Limit {
+                variable: "number of nodes in the tree".to_string(),
+                lower: inclusive("0", 0),
+                upper: inclusive("10^4", 10_000),
+            }]
+        );
+    }
+
+    #[test]
+    fn skips_items_that_are_not_limits() {
+        assert!(parse_limit("s[i] is '<' or '>'").is_empty());
+        assert!(parse_limit("s consists of lowercase English letters.").is_empty());
+        assert!(parse_limit("nums.length == n").is_empty());
+    }
+
+    #[test]
+    fn reads_the_constraints_list() {
+        let html = "<p>Given an array.</p><p><strong>Constraints:</strong></p>\n<ul>\n\
+            <li><code>2 &lt;= nums.length &lt;= 10<sup>4</sup></code></li>\n\
+            <li>Only one valid answer exists.</li>\n</ul>";
+        assert_eq!(
+            parse_statement(html).limits,
+            vec![Limit {
+                variable: "nums.length".to_string(),
+                lower: inclusive("2", 2),
+                upper: inclusive("10^4", 10_000),
+            }]
+        );
+    }
+}
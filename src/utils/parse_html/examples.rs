@@ -0,0 +1,194 @@
+use super::{plain_text, Block};
+use crate::types::Example;
+
+/// Pulls the worked examples out of a statement's blocks. They are written either as
+/// `<pre>` blocks or, in newer statements, as one paragraph per line, each line
+/// starting with `Input:`, `Output:` or `Explanation:`.
+pub fn examples_in(blocks: &[Block]) -> Vec<Example> {
+    let mut examples = Vec::new();
+    let mut current: Option<ExampleLines> = None;
+    let mut field = None;
+
+    for block in blocks {
+        // an explanation can go on over paragraphs and lists, the other fields
+        // only over the lines of their own block
+        if field != Some(Field::Explanation) {
+            field = None;
+        }
+        let Some(block_lines) = text_lines(block) else {
+            field = None;
+            continue;
+        };
+        for line in block_lines {
+            let line = line.trim();
+            if is_section_start(line) {
+                examples.extend(current.take().and_then(ExampleLines::build));
+                field = None;
+            } else if let Some(rest) = line.strip_prefix("Input:") {
+                examples.extend(current.take().and_then(ExampleLines::build));
+                current = Some(ExampleLines::default());
+                field = Some(Field::Input);
+                push_line(&mut current, field, rest);
+            } else if let Some(rest) = line.strip_prefix("Output:") {
+                field = Some(Field::Output);
+                push_line(&mut current, field, rest);
+            } else if let Some(rest) = line.strip_prefix("Explanation:") {
+                field = Some(Field::Explanation);
+                push_line(&mut current, field, rest);
+            } else {
+                push_line(&mut current, field, line);
+            }
+        }
+    }
+    examples.extend(current.and_then(ExampleLines::build));
+    examples
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Field {
+    Input,
+    Output,
+    Explanation,
+}
+
+/// The lines of an example found so far.
+#[derive(Default)]
+struct ExampleLines {
+    input: Vec<String>,
+    output: Vec<String>,
+    explanation: Vec<String>,
+}
+
+impl ExampleLines {
+    /// An example needs at least an input and an output.
+    fn build(self) -> Option<Example> {
+        if self.input.is_empty() || self.output.is_empty() {
+            return None;
+        }
+        Some(Example {
+            input: self.input.join("\n"),
+            output: self.output.join("\n"),
+            explanation: (!self.explanation.is_empty()).then(|| self.explanation.join("\n")),
+        })
+    }
+}
+
+fn push_line(example: &mut Option<ExampleLines>, field: Option<Field>, line: &str) {
+    let (Some(example), Some(field)) = (example, field) else {
+        return;
+    };
+    let line = line.trim();
+    if line.is_empty() {
+        return;
+    }
+    let lines = match field {
+        Field::Input => &mut example.input,
+        Field::Output => &mut example.output,
+        Field::Explanation => &mut example.explanation,
+    };
+    lines.push(line.to_string());
+}
+
+/// Lines like `Example 2:` or `Constraints:` that end the example before them.
+fn is_section_start(line: &str) -> bool {
+    (line.starts_with("Example") && line.ends_with(':'))
+        || ["Constraints", "Note", "Follow"]
+            .iter()
+            .any(|section| line.starts_with(section))
+}
+
+/// The plain text lines of a block that can hold an example line, or `None`
+/// for blocks like headings and tables that end one.
+fn text_lines(block: &Block) -> Option<Vec<String>> {
+    match block {
+        Block::Code(lines) => Some(
+            lines
+                .iter()
+                .map(|line| line.iter().map(plain_text).collect())
+                .collect(),
+        ),
+        Block::Text(content) | Block::Paragraph(content) => {
+            let text: String = content.iter().map(plain_text).collect();
+            Some(text.lines().map(str::to_string).collect())
+        }
+        Block::List { items, .. } => {
+            let mut lines = Vec::new();
+            for block in items.iter().flatten() {
+                lines.extend(text_lines(block)?);
+            }
+            Some(lines)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::parse_html::parse_statement;
+
+    fn parse_examples(html: &str) -> Vec<Example> {
+        parse_statement(html).examples
+    }
+
+    fn example(input: &str, output: &str, explanation: Option<&str>) -> Example {
+        Example {
+            input: input.to_string(),
+            output: output.to_string(),
+            explanation: explanation.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn reads_pre_blocks() {
+        let html = "<p><strong class=\"example\">Example 1:</strong></p>\n<pre>\n\
+            <strong>Input:</strong> nums = [2,7,11,15], target = 9\n\
+            <strong>Output:</strong> [0,1]\n\
+            <strong>Explanation:</strong> Because nums[0] + nums[1] == 9, we return [0, 1].\n</pre>\n\
+            <p><strong class=\"example\">Example 2:</strong></p>\n<pre>\n\
+            <strong>Input:</strong> nums = [3,3], target = 6\n\
+            <strong>Output:</strong> [0,1]\n</pre>\n\
+            <p><strong>Constraints:</strong></p>\n<ul><li><code>2 &lt;= nums.length</code></li></ul>";
+        assert_eq!(
+            parse_examples(html),
+            [
+                example(
+                    "nums = [2,7,11,15], target = 9",
+                    "[0,1]",
+                    Some("Because nums[0] + nums[1] == 9, we return [0, 1].")
+                ),
+                example("nums = [3,3], target = 6", "[0,1]", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_superscripts_on_their_line() {
+        let html = "<pre>\n<strong>Input:</strong> n = 2<sup>31</sup>\n<strong>Output:</strong> 1\n</pre>";
+        assert_eq!(parse_examples(html), [example("n = 2^31", "1", None)]);
+    }
+
+    #[test]
+    fn reads_paragraph_examples_with_long_explanations() {
+        let html = "<p><strong class=\"example\">Example 1:</strong></p>\n\
+            <div class=\"example-block\">\n\
+            <p><strong>Input:</strong> <span class=\"example-io\">s = \"abc\"</span></p>\n\
+            <p><strong>Output:</strong> <span class=\"example-io\">3</span></p>\n\
+            <p><strong>Explanation:</strong></p>\n\
+            <p>The substrings are:</p>\n\
+            <ul><li><code>\"a\"</code></li><li><code>\"b\"</code></li></ul>\n\
+            </div>\n\
+            <p><strong>Constraints:</strong></p>";
+        assert_eq!(
+            parse_examples(html),
+            [example("s = \"abc\"", "3", Some("The substrings are:\n\"a\"\n\"b\""))]
+        );
+    }
+
+    #[test]
+    fn needs_an_input_and_an_output() {
+        assert!(parse_examples("<p>Input: only an input</p>").is_empty());
+        assert!(parse_examples("<p>Output: 3</p>").is_empty());
+        assert!(parse_examples("").is_empty());
+    }
+}
@@ -0,0 +1,330 @@
+use super::{notation, plain_text, question_blocks, Block, Inline, Marks, TableRow};
+use crate::types::Question;
+
+/// `question` as GitHub-flavored Markdown, e.g. for a README. Hints are folded
+/// away in a `<details>` section so they aren't read by accident.
+pub fn question_to_markdown(question: &Question) -> String {
+    blocks_to_markdown(&question_blocks(question), false).join("\n") + "\n"
+}
+
+/// The lines of `blocks`, separated by blank lines. In list items only consecutive
+/// paragraphs need one, so the list stays tight.
+fn blocks_to_markdown(blocks: &[Block], in_item: bool) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut previous_is_text = false;
+    for block in blocks {
+        let block_lines = block_to_markdown(block);
+        if block_lines.is_empty() {
+            continue;
+        }
+        let is_text = matches!(block, Block::Text(_) | Block::Paragraph(_));
+        if !lines.is_empty() && (!in_item || (previous_is_text && is_text)) {
+            lines.push(String::new());
+        }
+        lines.extend(block_lines);
+        previous_is_text = is_text;
+    }
+    lines
+}
+
+fn block_to_markdown(block: &Block) -> Vec<String> {
+    match block {
+        Block::Text(content) | Block::Paragraph(content) => {
+            let text = inline_markdown(content, "\\\n");
+            if text.trim().is_empty() {
+                return Vec::new();
+            }
+            text.lines().map(|line| escape_line_start(line.trim())).collect()
+        }
+        Block::Heading(level, content) => {
+            let text = inline_markdown(content, " ");
+            if text.trim().is_empty() {
+                return Vec::new();
+            }
+            vec![format!("{} {}", "#".repeat(*level), text.trim())]
+        }
+        Block::Code(lines) => {
+            let text: Vec<String> = lines
+                .iter()
+                .map(|line| line.iter().map(plain_text).collect())
+                .collect();
+            // the fence has to be longer than any run of backticks inside
+            let fence = "`".repeat(longest_backtick_run(&text.join("\n")).max(2) + 1);
+            let mut code = vec![fence.clone()];
+            code.extend(text);
+            code.push(fence);
+            code
+        }
+        Block::List { ordered, start, items } => {
+            let mut lines = Vec::new();
+            for (i, item) in items.iter().enumerate() {
+                let marker = if *ordered { format!("{}. ", start.saturating_add(i as i64)) } else { "- ".to_string() };
+                let hanging = " ".repeat(marker.len());
+                let item_lines = blocks_to_markdown(item, true);
+                if item_lines.is_empty() {
+                    lines.push(marker.trim_end().to_string());
+                }
+                for (j, line) in item_lines.into_iter().enumerate() {
+                    lines.push(match j {
+                        0 => format!("{}{}", marker, line),
+                        _ if line.is_empty() => line,
+                        _ => format!("{}{}", hanging, line),
+                    });
+                }
+            }
+            lines
+        }
+        Block::Quote(blocks) => blocks_to_markdown(blocks, false)
+            .into_iter()
+            .map(|line| if line.is_empty() { ">".to_string() } else { format!("> {}", line) })
+            .collect(),
+        Block::Table(rows) => table_to_markdown(rows),
+        Block::Rule => vec!["---".to_string()],
+        Block::Details { summary, blocks } => {
+            let mut lines = vec![
+                "<details>".to_string(),
+                format!("<summary>{}</summary>", html_escape::encode_text(summary)),
+                String::new(),
+            ];
+            lines.extend(blocks_to_markdown(blocks, false));
+            lines.push(String::new());
+            lines.push("</details>".to_string());
+            lines
+        }
+    }
+}
+
+/// GitHub tables always have a header, so the first row is used as one.
+fn table_to_markdown(rows: &[TableRow]) -> Vec<String> {
+    let cells: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.cells
+                .iter()
+                .map(|cell| inline_markdown(&cell.content, "<br>").trim().replace('|', "\\|"))
+                .collect()
+        })
+        .collect();
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    let row_line = |row: &Vec<String>| {
+        let mut row: Vec<&str> = row.iter().map(String::as_str).collect();
+        row.resize(columns, "");
+        format!("| {} |", row.join(" | "))
+    };
+
+    let mut lines = Vec::new();
+    for (i, row) in cells.iter().enumerate() {
+        lines.push(row_line(row));
+        if i == 0 {
+            lines.push(format!("|{}|", vec![" --- "; columns].join("|")));
+        }
+    }
+    lines
+}
+
+/// Markdown waiting to be written: text that still needs its marks and escaping,
+/// or finished markup.
+enum Piece {
+    Text(String, Marks),
+    Markup(String),
+}
+
+fn inline_markdown(inlines: &[Inline], line_break: &str) -> String {
+    let mut pieces = Vec::new();
+    collect_pieces(inlines, line_break, &mut pieces);
+    pieces
+        .iter()
+        .map(|piece| match piece {
+            Piece::Text(text, marks) => format_text(text, *marks),
+            Piece::Markup(markup) => markup.clone(),
+        })
+        .collect()
+}
+
+fn collect_pieces(inlines: &[Inline], line_break: &str, pieces: &mut Vec<Piece>) {
+    for inline in inlines {
+        match inline {
+            Inline::Text(text, marks) => push_text(pieces, text, *marks),
+            Inline::Superscript(text, marks) => push_text(pieces, &notation('^', text), *marks),
+            Inline::Subscript(text, marks) => push_text(pieces, &notation('_', text), *marks),
+            Inline::Image { alt, src: Some(src) } => pieces.push(Piece::Markup(format!(
+                "![{}]({})",
+                escape(alt.as_deref().unwrap_or_default()),
+                link_destination(src)
+            ))),
+            Inline::Image { alt, src: None } => {
+                if let Some(alt) = alt {
+                    push_text(pieces, alt, Marks::default());
+                }
+            }
+            Inline::Link { href: Some(href), content } => pieces.push(Piece::Markup(format!(
+                "[{}]({})",
+                inline_markdown(content, line_break).trim(),
+                link_destination(href)
+            ))),
+            Inline::Link { href: None, content } => collect_pieces(content, line_break, pieces),
+            Inline::Break => pieces.push(Piece::Markup(line_break.to_string())),
+        }
+    }
+}
+
+/// Adds `text`, merging it with the previous text of the same marks so emphasis
+/// isn't closed and reopened, e.g. around a superscript in a code span.
+fn push_text(pieces: &mut Vec<Piece>, text: &str, marks: Marks) {
+    if let Some(Piece::Text(previous, previous_marks)) = pieces.last_mut()
+        && *previous_marks == marks
+    {
+        previous.push_str(text);
+        return;
+    }
+    pieces.push(Piece::Text(text.to_string(), marks));
+}
+
+fn format_text(text: &str, marks: Marks) -> String {
+    // delimiters have to touch the text they enclose
+    let core = text.trim_matches(' ');
+    if core.is_empty() {
+        return text.to_string();
+    }
+    let leading = &text[..text.len() - text.trim_start_matches(' ').len()];
+    let trailing = &text[text.trim_end_matches(' ').len()..];
+
+    let mut formatted = if marks.code { code_span(core) } else { escape(core) };
+    if marks.strike {
+        formatted = format!("~~{}~~", formatted);
+    }
+    if marks.italic {
+        formatted = format!("*{}*", formatted);
+    }
+    if marks.bold {
+        formatted = format!("**{}**", formatted);
+    }
+    format!("{}{}{}", leading, formatted, trailing)
+}
+
+fn code_span(text: &str) -> String {
+    let delimiter = "`".repeat(longest_backtick_run(text) + 1);
+    // a backtick next to the delimiter would be read as part of it
+    let padding = if text.starts_with('`') || text.ends_with('`') { " " } else { "" };
+    format!("{delimiter}{padding}{text}{padding}{delimiter}")
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+fn link_destination(url: &str) -> String {
+    if url.contains([' ', '(', ')']) {
+        format!("<{}>", url)
+    } else {
+        url.to_string()
+    }
+}
+
+/// Escapes the characters that would otherwise start emphasis, code, links or tags.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let opens_tag = c == '<'
+            && chars
+                .peek()
+                .is_some_and(|next| next.is_ascii_alphabetic() || matches!(next, '/' | '!' | '?'));
+        if matches!(c, '\\' | '`' | '*' | '_' | '[' | ']') || opens_tag {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Escapes what would make a line of text a heading, quote, list item or rule.
+fn escape_line_start(line: &str) -> String {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let followed_by_space = |rest: &str| rest.is_empty() || rest.starts_with(' ');
+
+    if digits > 0
+        && line[digits..].starts_with(['.', ')'])
+        && followed_by_space(&line[digits + 1..])
+    {
+        // `1. ` would start an ordered list
+        return format!("{}\\{}", &line[..digits], &line[digits..]);
+    }
+    let is_rule = line.len() > 1 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='));
+    if is_rule || (line.starts_with(['#', '>', '-', '+']) && followed_by_space(&line[1..])) {
+        return format!("\\{}", line);
+    }
+    line.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::QuestionSource;
+
+    fn question(content: &str, hints: Option<Vec<String>>) -> Question {
+        Question {
+            title: "Two Sum".to_string(),
+            title_slug: "two-sum".to_string(),
+            content: content.to_string(),
+            difficulty: None,
+            topic: None,
+            hints,
+            example_testcase_list: None,
+            similar_question_list: None,
+            next_challenges: None,
+            source: QuestionSource::Synced,
+            parsed: Default::default(),
+        }
+    }
+
+    fn markdown(content: &str) -> String {
+        question_to_markdown(&question(content, None))
+    }
+
+    #[test]
+    fn writes_the_title_and_statement() {
+        assert_eq!(
+            markdown("<p>Given an array <code>nums</code>, return <strong>two</strong> indices.</p>"),
+            "# Two Sum\n\nGiven an array `nums`, return **two** indices.\n"
+        );
+    }
+
+    #[test]
+    fn fences_are_longer_than_the_backticks_inside() {
+        assert_eq!(markdown("<pre>a ``` b</pre>"), "# Two Sum\n\n````\na ``` b\n````\n");
+        assert_eq!(markdown("<pre>plain</pre>"), "# Two Sum\n\n```\nplain\n```\n");
+    }
+
+    #[test]
+    fn writes_superscripts_with_a_caret() {
+        assert_eq!(
+            markdown("<p><code>1 &lt;= n &lt;= 10<sup>4</sup></code></p>"),
+            "# Two Sum\n\n`1 <= n <= 10^4`\n"
+        );
+    }
+
+    #[test]
+    fn writes_tables_with_a_header() {
+        assert_eq!(
+            markdown("<table><tr><th>n</th><th>a|b</th></tr><tr><td>1</td><td>2</td></tr></table>"),
+            "# Two Sum\n\n| n | a\\|b |\n| --- | --- |\n| 1 | 2 |\n"
+        );
+    }
+
+    #[test]
+    fn folds_the_hints_away() {
+        let hints = Some(vec!["Use a <code>HashMap</code>.".to_string()]);
+        assert_eq!(
+            question_to_markdown(&question("<p>Statement.</p>", hints)),
+            "# Two Sum\n\nStatement.\n\n<details>\n<summary>Hints</summary>\n\n1. Use a `HashMap`.\n\n</details>\n"
+        );
+    }
+
+    #[test]
+    fn numbers_lists_from_their_start() {
+        assert_eq!(markdown("<ol start=\"3\"><li>a</li><li>b</li></ol>"), "# Two Sum\n\n3. a\n4. b\n");
+        let huge = markdown(&format!("<ol start=\"{}\"><li>a</li><li>b</li></ol>", i64::MAX));
+        assert!(huge.ends_with(&format!("{max}. a\n{max}. b\n", max = i64::MAX)));
+    }
+}
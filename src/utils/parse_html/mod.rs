@@ -0,0 +1,554 @@
+mod constraints;
+mod examples;
+mod markdown;
+mod terminal;
+
+use html_parser::{Dom, Element, Node};
+
+use crate::types::{ParsedStatement, Question, QuestionSource};
+
+pub use markdown::question_to_markdown;
+pub use terminal::{limits_to_lines, parse_html_to_lines, question_to_lines};
+
+// Statements are walked once into blocks and inlines; the backends only differ
+// in how they lay those out, as terminal lines or as Markdown.
+
+/// Inline formatting, accumulated over nested elements.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Marks {
+    bold: bool,
+    italic: bool,
+    underline: bool,
+    strike: bool,
+    code: bool,
+}
+
+impl Marks {
+    /// These marks plus the formatting of the element called `name`, if any.
+    fn with(mut self, name: &str) -> Self {
+        match name {
+            "strong" | "b" => self.bold = true,
+            "em" | "i" => self.italic = true,
+            "u" | "ins" => self.underline = true,
+            "s" | "del" | "strike" => self.strike = true,
+            "code" | "kbd" | "tt" => self.code = true,
+            _ => {}
+        }
+        self
+    }
+}
+
+#[derive(Debug)]
+enum Inline {
+    /// Whitespace runs are collapsed to one space, except in code blocks.
+    Text(String, Marks),
+    Superscript(String, Marks),
+    Subscript(String, Marks),
+    Image {
+        alt: Option<String>,
+        src: Option<String>,
+    },
+    /// `href` is `None` for in-page anchors, which have nowhere to point to.
+    Link {
+        href: Option<String>,
+        content: Vec<Inline>,
+    },
+    Break,
+}
+
+#[derive(Debug)]
+enum Block {
+    /// Text outside of any paragraph, e.g. directly inside a list item.
+    Text(Vec<Inline>),
+    /// Empty for spacer paragraphs like `<p>&nbsp;</p>`.
+    Paragraph(Vec<Inline>),
+    Heading(usize, Vec<Inline>),
+    /// The lines of a `<pre>` block.
+    Code(Vec<Vec<Inline>>),
+    List {
+        ordered: bool,
+        start: i64,
+        items: Vec<Vec<Block>>,
+    },
+    Quote(Vec<Block>),
+    Table(Vec<TableRow>),
+    Rule,
+    /// Content to fold away where the output allows it, like hints.
+    Details {
+        summary: String,
+        blocks: Vec<Block>,
+    },
+}
+
+#[derive(Debug)]
+struct TableRow {
+    cells: Vec<TableCell>,
+}
+
+#[derive(Debug)]
+struct TableCell {
+    /// A `<th>` rather than a `<td>`.
+    header: bool,
+    content: Vec<Inline>,
+}
+
+/// Everything shown about a question: a header, the statement, then its hints,
+/// example test cases and similar questions.
+fn question_blocks(question: &Question) -> Vec<Block> {
+    let text = |s: &str| Inline::Text(s.to_string(), Marks::default());
+    let mut blocks = vec![Block::Heading(1, vec![text(&question.title)])];
+
+    let mut facts = Vec::new();
+    if let Some(difficulty) = &question.difficulty {
+        facts.push(("Difficulty:", difficulty.clone()));
+    }
+    if let Some(topics) = question.topic.as_ref().filter(|topics| !topics.is_empty()) {
+        let names: Vec<&str> = topics.iter().map(|t| t.name.as_str()).collect();
+        facts.push(("Topics:", names.join(", ")));
+    }
+    if question.source != QuestionSource::Synced {
+        facts.push(("Source:", question.source.to_string()));
+    }
+    let mut header = Vec::new();
+    for (name, value) in facts {
+        if !header.is_empty() {
+            header.push(text(" · "));
+        }
+        header.push(Inline::Text(name.to_string(), Marks::default().with("strong")));
+        header.push(text(&format!(" {}", value)));
+    }
+    if !header.is_empty() {
+        blocks.push(Block::Paragraph(header));
+    }
+
+    blocks.extend(parse_blocks(&question.content));
+
+    if let Some(hints) = question.hints.as_ref().filter(|hints| !hints.is_empty()) {
+        blocks.push(Block::Details {
+            summary: "Hints".to_string(),
+            blocks: vec![Block::List {
+                ordered: true,
+                start: 1,
+                items: hints.iter().map(|hint| parse_blocks(hint)).collect(),
+            }],
+        });
+    }
+    if let Some(examples) = question.example_testcase_list.as_ref().filter(|e| !e.is_empty()) {
+        blocks.push(Block::Heading(2, vec![text("Example test cases")]));
+        for example in examples {
+            blocks.push(Block::Code(example.lines().map(|line| vec![text(line)]).collect()));
+        }
+    }
+    if let Some(similar) = question.similar_question_list.as_ref().filter(|s| !s.is_empty()) {
+        blocks.push(Block::Heading(2, vec![text("Similar questions")]));
+        let items = similar
+            .iter()
+            .map(|q| {
+                vec![Block::Text(vec![
+                    text(&format!("{} ", q.title)),
+                    Inline::Text(q.title_slug.clone(), Marks::default().with("code")),
+                ])]
+            })
+            .collect();
+        blocks.push(Block::List {
+            ordered: false,
+            start: 1,
+            items,
+        });
+    }
+    blocks
+}
+
+/// The examples and limits of a statement, see [`Question::examples`].
+pub fn parse_statement(html: &str) -> ParsedStatement {
+    let blocks = parse_blocks(html);
+    ParsedStatement {
+        examples: examples::examples_in(&blocks),
+        limits: constraints::limits_in(blocks),
+    }
+}
+
+fn parse_blocks(html: &str) -> Vec<Block> {
+    let dom = Dom::parse(html)
+        .unwrap_or_else(|e| Dom::parse(&format!("Error parsing HTML: {}", e)).unwrap());
+    walk_blocks(&dom.children, Marks::default())
+}
+
+/// Groups `nodes` into blocks; inline content between block elements becomes a `Block::Text`.
+fn walk_blocks(nodes: &[Node], marks: Marks) -> Vec<Block> {
+    let mut walker = BlockWalker::default();
+    walker.walk(nodes, marks);
+    walker.end_text();
+    walker.blocks
+}
+
+/// The inline content of `nodes`, for elements that can't hold blocks like headings and links.
+fn walk_inlines(nodes: &[Node], marks: Marks) -> Vec<Inline> {
+    flatten(walk_blocks(nodes, marks))
+}
+
+#[derive(Default)]
+struct BlockWalker {
+    blocks: Vec<Block>,
+    /// Inline content since the last block.
+    inlines: Vec<Inline>,
+}
+
+impl BlockWalker {
+    fn walk(&mut self, nodes: &[Node], marks: Marks) {
+        for (i, node) in nodes.iter().enumerate() {
+            if i > 0 && dropped_whitespace(&nodes[i - 1], node).is_some() {
+                self.inlines.push(Inline::Text(" ".to_string(), marks));
+            }
+            match node {
+                Node::Text(text) => {
+                    let text = collapse_whitespace(&html_escape::decode_html_entities(text));
+                    if !text.is_empty() {
+                        self.inlines.push(Inline::Text(text, marks));
+                    }
+                }
+                Node::Element(element) => self.walk_element(element, marks),
+                Node::Comment(_) => {}
+            }
+        }
+    }
+
+    fn walk_element(&mut self, element: &Element, marks: Marks) {
+        match element.name.as_str() {
+            "p" => {
+                self.end_text();
+                if is_blank_paragraph(element) {
+                    self.blocks.push(Block::Paragraph(Vec::new()));
+                    return;
+                }
+                // a paragraph wrapping blocks of its own is replaced by them
+                for block in walk_blocks(&element.children, marks) {
+                    self.blocks.push(match block {
+                        Block::Text(content) => Block::Paragraph(content),
+                        block => block,
+                    });
+                }
+            }
+            "div" | "section" | "article" | "header" | "footer" => {
+                self.end_text();
+                self.blocks.extend(walk_blocks(&element.children, marks));
+            }
+            "br" => self.inlines.push(Inline::Break),
+            "hr" => self.push_block(Block::Rule),
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = element.name[1..].parse().unwrap_or(1);
+                self.push_block(Block::Heading(level, walk_inlines(&element.children, marks)));
+            }
+            "blockquote" => self.push_block(Block::Quote(walk_blocks(&element.children, marks))),
+            "pre" => self.push_block(Block::Code(code_lines(&element.children, marks))),
+            "ul" | "ol" => self.push_block(walk_list(element, marks)),
+            // an item outside of any list
+            "li" => self.push_block(Block::List {
+                ordered: false,
+                start: 1,
+                items: vec![walk_blocks(&element.children, marks)],
+            }),
+            "table" => {
+                let mut rows = Vec::new();
+                collect_table_rows(&element.children, marks, &mut rows);
+                if !rows.is_empty() {
+                    self.push_block(Block::Table(rows));
+                }
+            }
+            "img" => self.inlines.push(Inline::Image {
+                alt: attribute(element, "alt")
+                    .map(str::trim)
+                    .filter(|alt| !alt.is_empty())
+                    .map(str::to_string),
+                src: attribute(element, "src").map(str::to_string),
+            }),
+            "a" => self.inlines.push(Inline::Link {
+                href: attribute(element, "href")
+                    .filter(|href| !href.is_empty() && !href.starts_with('#'))
+                    .map(str::to_string),
+                content: walk_inlines(&element.children, marks),
+            }),
+            "sup" => self.inlines.push(Inline::Superscript(element_text(element), marks)),
+            "sub" => self.inlines.push(Inline::Subscript(element_text(element), marks)),
+            "script" | "style" | "head" => {}
+            name => self.walk(&element.children, marks.with(name)),
+        }
+    }
+
+    fn push_block(&mut self, block: Block) {
+        self.end_text();
+        self.blocks.push(block);
+    }
+
+    fn end_text(&mut self) {
+        // whitespace between two blocks isn't text
+        let has_text = self
+            .inlines
+            .iter()
+            .any(|inline| !matches!(inline, Inline::Text(text, _) if text.trim().is_empty()));
+        let inlines = std::mem::take(&mut self.inlines);
+        if has_text {
+            self.blocks.push(Block::Text(inlines));
+        }
+    }
+}
+
+fn walk_list(list: &Element, marks: Marks) -> Block {
+    let items = list
+        .children
+        .iter()
+        .filter_map(|node| match node {
+            Node::Element(e) if e.name == "li" => Some(walk_blocks(&e.children, marks)),
+            _ => None,
+        })
+        .collect();
+
+    Block::List {
+        ordered: list.name == "ol",
+        start: attribute(list, "start")
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(1),
+        items,
+    }
+}
+
+fn collect_table_rows(nodes: &[Node], marks: Marks, rows: &mut Vec<TableRow>) {
+    for node in nodes {
+        let Node::Element(element) = node else {
+            continue;
+        };
+        match element.name.as_str() {
+            "thead" | "tbody" | "tfoot" => collect_table_rows(&element.children, marks, rows),
+            "tr" => {
+                let cells: Vec<TableCell> = element
+                    .children
+                    .iter()
+                    .filter_map(|node| match node {
+                        Node::Element(e) if e.name == "td" || e.name == "th" => Some(TableCell {
+                            header: e.name == "th",
+                            content: walk_inlines(&e.children, marks),
+                        }),
+                        _ => None,
+                    })
+                    .collect();
+                if !cells.is_empty() {
+                    rows.push(TableRow { cells });
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Splits the content of a `<pre>` block into lines, keeping its whitespace.
+fn code_lines(nodes: &[Node], marks: Marks) -> Vec<Vec<Inline>> {
+    let mut lines = vec![Vec::new()];
+    walk_code(nodes, marks, &mut lines);
+
+    let is_blank = |line: &Vec<Inline>| {
+        line.iter()
+            .all(|inline| matches!(inline, Inline::Text(text, _) if text.trim().is_empty()))
+    };
+    // The html-parser might introduce a leading newline from formatting.
+    if lines.first().is_some_and(is_blank) {
+        lines.remove(0);
+    }
+    // Also remove trailing empty line if it only contains whitespace
+    if lines.last().is_some_and(is_blank) {
+        lines.pop();
+    }
+    lines
+}
+
+fn walk_code(nodes: &[Node], marks: Marks, lines: &mut Vec<Vec<Inline>>) {
+    for (i, node) in nodes.iter().enumerate() {
+        match i.checked_sub(1).and_then(|p| dropped_whitespace(&nodes[p], node)) {
+            Some(Gap::Lines(count)) => lines.extend((0..count).map(|_| Vec::new())),
+            Some(Gap::Spaces(count)) => {
+                lines.last_mut().unwrap().push(Inline::Text(" ".repeat(count), marks))
+            }
+            None => {}
+        }
+        match node {
+            Node::Text(text) => {
+                // Decode entities before splitting into lines.
+                let decoded = html_escape::decode_html_entities(text);
+                let mut parts = decoded.split('\n');
+
+                // The first part belongs to the current line, each other part starts a new one.
+                if let Some(first) = parts.next().filter(|s| !s.is_empty()) {
+                    lines.last_mut().unwrap().push(Inline::Text(first.to_string(), marks));
+                }
+                for part in parts {
+                    let mut line = Vec::new();
+                    if !part.is_empty() {
+                        line.push(Inline::Text(part.to_string(), marks));
+                    }
+                    lines.push(line);
+                }
+            }
+            Node::Element(element) => match element.name.as_str() {
+                "br" => lines.push(Vec::new()),
+                "sup" => lines
+                    .last_mut()
+                    .unwrap()
+                    .push(Inline::Superscript(element_text(element), marks)),
+                "sub" => lines
+                    .last_mut()
+                    .unwrap()
+                    .push(Inline::Subscript(element_text(element), marks)),
+                name => walk_code(&element.children, marks.with(name), lines),
+            },
+            Node::Comment(_) => {}
+        }
+    }
+}
+
+/// Whitespace found between two elements.
+enum Gap {
+    Lines(usize),
+    Spaces(usize),
+}
+
+/// The whitespace between two sibling elements with nothing else in between.
+/// html_parser drops text nodes holding only whitespace, but it can still be
+/// told apart from the elements' positions, e.g. the line break after `10<sup>5</sup>`.
+fn dropped_whitespace(previous: &Node, node: &Node) -> Option<Gap> {
+    let (Node::Element(previous), Node::Element(element)) = (previous, node) else {
+        return None;
+    };
+    let (before, after) = (&previous.source_span, &element.source_span);
+    if after.start_line > before.end_line {
+        Some(Gap::Lines(after.start_line - before.end_line))
+    } else if after.start_line == before.end_line && after.start_column > before.end_column {
+        Some(Gap::Spaces(after.start_column - before.end_column))
+    } else {
+        None
+    }
+}
+
+/// Joins the text of `blocks` into one run, separated by spaces.
+fn flatten(blocks: Vec<Block>) -> Vec<Inline> {
+    let runs = blocks
+        .into_iter()
+        .map(|block| match block {
+            Block::Text(content) | Block::Paragraph(content) | Block::Heading(_, content) => content,
+            Block::Code(lines) => join_runs(lines),
+            Block::List { items, .. } => flatten(items.into_iter().flatten().collect()),
+            Block::Quote(blocks) | Block::Details { blocks, .. } => flatten(blocks),
+            Block::Table(rows) => join_runs(
+                rows.into_iter()
+                    .flat_map(|row| row.cells)
+                    .map(|cell| cell.content)
+                    .collect(),
+            ),
+            Block::Rule => Vec::new(),
+        })
+        .collect();
+    join_runs(runs)
+}
+
+fn join_runs(runs: Vec<Vec<Inline>>) -> Vec<Inline> {
+    let mut joined = Vec::new();
+    for run in runs.into_iter().filter(|run| !run.is_empty()) {
+        if !joined.is_empty() {
+            joined.push(Inline::Text(" ".to_string(), Marks::default()));
+        }
+        joined.extend(run);
+    }
+    joined
+}
+
+/// `10^5` and `x_i`, with parentheses when the raised part is more than a word: `2^(n-1)`.
+fn notation(marker: char, text: &str) -> String {
+    if !text.is_empty() && text.chars().all(char::is_alphanumeric) {
+        format!("{}{}", marker, text)
+    } else {
+        format!("{}({})", marker, text)
+    }
+}
+
+/// The text of `inline` without any markup, e.g. inside a fenced block.
+fn plain_text(inline: &Inline) -> String {
+    match inline {
+        Inline::Text(text, _) => text.clone(),
+        Inline::Superscript(text, _) => notation('^', text),
+        Inline::Subscript(text, _) => notation('_', text),
+        Inline::Image { alt, .. } => alt.clone().unwrap_or_default(),
+        Inline::Link { content, .. } => content.iter().map(plain_text).collect(),
+        Inline::Break => "\n".to_string(),
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut after_space = false;
+    for c in text.chars() {
+        // non-breaking spaces are kept as they are
+        if c.is_whitespace() && c != '\u{a0}' {
+            if !after_space {
+                collapsed.push(' ');
+            }
+            after_space = true;
+        } else {
+            collapsed.push(c);
+            after_space = false;
+        }
+    }
+    collapsed
+}
+
+fn attribute<'a>(element: &'a Element, name: &str) -> Option<&'a str> {
+    element.attributes.get(name).and_then(|value| value.as_deref())
+}
+
+/// The decoded text inside `element`, without any markup.
+fn element_text(element: &Element) -> String {
+    let mut text = String::new();
+    for node in &element.children {
+        match node {
+            Node::Text(t) => text.push_str(&html_escape::decode_html_entities(t)),
+            Node::Element(e) => text.push_str(&element_text(e)),
+            Node::Comment(_) => {}
+        }
+    }
+    text.trim().to_string()
+}
+
+/// Checks if a <p> element only contains &nbsp; or is empty.
+fn is_blank_paragraph(p_element: &Element) -> bool {
+    if p_element.children.is_empty() {
+        return true;
+    }
+    if p_element.children.len() == 1
+        && let Some(Node::Text(text)) = p_element.children.first()
+    {
+        let decoded = html_escape::decode_html_entities(text);
+        return decoded.trim_matches(|c: char| c.is_whitespace() || c == '\u{a0}').is_empty();
+    }
+    false
+}
+
+/// Strips tags and decodes entities, collapsing whitespace. Cheap enough to run
+/// over every question at load time, unlike a full `Dom::parse`.
+pub fn html_to_plain_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => {
+                in_tag = false;
+                text.push(' ');
+            }
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    html_escape::decode_html_entities(&text)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
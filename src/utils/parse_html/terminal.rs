@@ -0,0 +1,781 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use unicode_width::UnicodeWidthChar;
+
+use super::{parse_blocks, question_blocks, Block, Inline, Marks, TableRow};
+use crate::types::{Bound, Limit, Question};
+
+/// Bullets of unordered lists, by nesting depth.
+const BULLETS: [&str; 3] = ["•", "◦", "▪"];
+
+/// Marks code and table lines that were cut to fit the width.
+const CLIP_MARKER: &str = "…";
+
+/// Lays out a question statement for the terminal: block elements become lines,
+/// inline elements styled spans. Links are numbered and listed at the end.
+///
+/// Text wraps at word boundaries to fit `width` columns, with list items hanging
+/// under their marker; code and tables keep their layout and are clipped instead.
+pub fn parse_html_to_lines(html: &str, width: usize) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(width);
+    renderer.render_blocks(&parse_blocks(html), Style::default());
+    renderer.finish()
+}
+
+/// Lays out everything shown about `question` like [`parse_html_to_lines`] does its statement.
+pub fn question_to_lines(question: &Question, width: usize) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(width);
+    renderer.render_blocks(&question_blocks(question), Style::default());
+    renderer.finish()
+}
+
+/// A line per limit, like `1 ≤ nums.length ≤ 10⁴`.
+pub fn limits_to_lines(limits: &[Limit]) -> Vec<Line<'static>> {
+    let bound_text = |bound: &Bound| shift_notation(&bound.text);
+    let operator = |bound: &Bound| if bound.inclusive { " ≤ " } else { " < " };
+    limits
+        .iter()
+        .map(|limit| {
+            let mut spans = Vec::new();
+            if let Some(lower) = &limit.lower {
+                spans.push(Span::raw(bound_text(lower)));
+                spans.push(Span::styled(operator(lower), Style::default().fg(Color::DarkGray)));
+            }
+            spans.push(Span::styled(
+                limit.variable.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ));
+            if let Some(upper) = &limit.upper {
+                spans.push(Span::styled(operator(upper), Style::default().fg(Color::DarkGray)));
+                spans.push(Span::raw(bound_text(upper)));
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Layout state while going through the blocks.
+#[derive(Default)]
+struct Renderer {
+    /// Columns the lines have to fit in.
+    width: usize,
+    lines: Vec<Line<'static>>,
+    /// The line being built, starting with `indent` once it has content.
+    current: Vec<Span<'static>>,
+    line_started: bool,
+    /// Leading spans of `current` that are indentation or a list marker rather than text.
+    prefix_len: usize,
+    /// Whether `current` starts with a list marker, which the item's first block continues.
+    marker_pending: bool,
+    /// Prefix of every line, e.g. the indentation of a list item's content.
+    indent: Vec<Span<'static>>,
+    /// Lists don't put blank lines between their items and nested blocks.
+    list_depth: usize,
+    /// Link targets, numbered from 1 in order of appearance.
+    links: Vec<String>,
+}
+
+impl Renderer {
+    fn new(width: usize) -> Self {
+        Renderer {
+            width,
+            ..Default::default()
+        }
+    }
+
+    fn render_blocks(&mut self, blocks: &[Block], style: Style) {
+        for block in blocks {
+            self.render_block(block, style);
+        }
+    }
+
+    fn render_block(&mut self, block: &Block, style: Style) {
+        match block {
+            Block::Text(content) => {
+                self.start_block();
+                self.render_inlines(content, style);
+                self.flush_line();
+            }
+            // spacer paragraphs only leave the blank line
+            Block::Paragraph(content) => {
+                self.start_block();
+                self.render_inlines(content, style);
+                self.end_block();
+            }
+            Block::Heading(level, content) => {
+                self.start_block();
+                let mut heading = style.fg(Color::Cyan).add_modifier(Modifier::BOLD);
+                if *level <= 2 {
+                    heading = heading.add_modifier(Modifier::UNDERLINED);
+                }
+                self.render_inlines(content, heading);
+                self.end_block();
+            }
+            Block::Code(lines) => {
+                self.flush_line();
+                for line in lines {
+                    let mut spans = self.indent.clone();
+                    spans.extend(line.iter().filter_map(|inline| code_span(inline, style)));
+                    self.lines.push(clip_spans(spans, self.width));
+                }
+                self.end_block();
+            }
+            Block::List { ordered, start, items } => self.render_list(*ordered, *start, items, style),
+            Block::Quote(blocks) => {
+                self.flush_line();
+                self.indent.push(Span::styled("│ ", Style::default().fg(Color::DarkGray)));
+                self.render_blocks(blocks, style.add_modifier(Modifier::ITALIC));
+                self.flush_line();
+                self.indent.pop();
+                self.end_block();
+            }
+            Block::Table(rows) => self.render_table(rows, style),
+            // a terminal can't fold, so the summary becomes a heading
+            Block::Details { summary, blocks } => {
+                let summary = Inline::Text(summary.clone(), Marks::default());
+                self.render_block(&Block::Heading(2, vec![summary]), style);
+                self.render_blocks(blocks, style);
+            }
+            Block::Rule => {
+                self.flush_line();
+                let available = self.width.saturating_sub(spans_width(&self.indent));
+                self.push_span(Span::styled("─".repeat(available.min(20)), Style::default().fg(Color::DarkGray)));
+                self.end_block();
+            }
+        }
+    }
+
+    fn render_inlines(&mut self, inlines: &[Inline], style: Style) {
+        for inline in inlines {
+            match inline {
+                Inline::Text(text, marks) => self.push_text(text, marks_style(*marks, style)),
+                Inline::Superscript(text, marks) => self.push_span(Span::styled(
+                    shift_text(text, superscript, '^'),
+                    marks_style(*marks, style),
+                )),
+                Inline::Subscript(text, marks) => self.push_span(Span::styled(
+                    shift_text(text, subscript, '_'),
+                    marks_style(*marks, style),
+                )),
+                Inline::Image { alt, .. } => {
+                    let label = match alt {
+                        Some(alt) => format!("[image: {}]", alt),
+                        None => "[image]".to_string(),
+                    };
+                    self.push_span(Span::styled(label, style.fg(Color::Magenta).add_modifier(Modifier::ITALIC)));
+                }
+                Inline::Link { href, content } => {
+                    self.render_inlines(content, style.fg(Color::Blue).add_modifier(Modifier::UNDERLINED));
+                    if let Some(href) = href {
+                        let number = self.link_number(href);
+                        self.push_span(Span::styled(format!("[{}]", number), Style::default().fg(Color::DarkGray)));
+                    }
+                }
+                Inline::Break => self.break_line(),
+            }
+        }
+    }
+
+    /// Adds inline text, collapsing whitespace runs the way browsers do.
+    fn push_text(&mut self, text: &str, style: Style) {
+        let mut collapsed = String::with_capacity(text.len());
+        let mut after_space =
+            !self.line_started || self.current.last().is_some_and(|s| s.content.ends_with(' '));
+
+        for c in text.chars() {
+            // non-breaking spaces are kept as they are
+            if c.is_whitespace() && c != '\u{a0}' {
+                if !after_space {
+                    collapsed.push(' ');
+                    after_space = true;
+                }
+            } else {
+                collapsed.push(c);
+                after_space = false;
+            }
+        }
+        if !collapsed.is_empty() {
+            self.push_span(Span::styled(collapsed, style));
+        }
+    }
+
+    fn push_span(&mut self, span: Span<'static>) {
+        if !self.line_started {
+            self.current.extend(self.indent.iter().cloned());
+            self.prefix_len = self.current.len();
+            self.line_started = true;
+        }
+        self.current.push(span);
+    }
+
+    /// Adds a span that belongs to the line's prefix, e.g. a list marker, so
+    /// wrapping doesn't treat it as text.
+    fn push_marker(&mut self, span: Span<'static>) {
+        self.push_span(span);
+        self.prefix_len = self.current.len();
+        self.marker_pending = true;
+    }
+
+    /// Ends the current line before a block, unless it only holds the list marker
+    /// the block should follow. Code and tables go below their marker instead.
+    fn start_block(&mut self) {
+        if !(self.marker_pending && self.current.len() == self.prefix_len) {
+            self.flush_line();
+        }
+    }
+
+    /// Ends the current line if it has any content, wrapping it to the width.
+    /// Wrapped lines start with the current indentation.
+    fn flush_line(&mut self) {
+        if self.line_started {
+            let mut prefix = std::mem::take(&mut self.current);
+            let text = prefix.split_off(self.prefix_len);
+            self.lines.extend(wrap_spans(prefix, text, &self.indent, self.width));
+            self.line_started = false;
+        }
+        self.current.clear();
+        self.marker_pending = false;
+    }
+
+    /// `<br>`: ends the current line even when it is empty.
+    fn break_line(&mut self) {
+        if !self.line_started {
+            self.push_span(Span::raw(""));
+        }
+        self.flush_line();
+    }
+
+    /// Ends a block, leaving one blank line before the next outside of lists.
+    fn end_block(&mut self) {
+        self.flush_line();
+        if self.list_depth == 0 && self.lines.last().is_some_and(|l| !l.spans.is_empty()) {
+            self.lines.push(Line::default());
+        }
+    }
+
+    fn render_list(&mut self, ordered: bool, start: i64, items: &[Vec<Block>], style: Style) {
+        self.start_block();
+        // numbers are right-aligned so the item texts line up
+        let number_width = start.saturating_add(items.len() as i64 - 1).to_string().len();
+        let bullet = BULLETS[self.list_depth % BULLETS.len()];
+
+        self.list_depth += 1;
+        for (i, item) in items.iter().enumerate() {
+            let marker = if ordered {
+                format!("{:>width$}. ", start.saturating_add(i as i64), width = number_width)
+            } else {
+                format!("{} ", bullet)
+            };
+            self.render_list_item(item, marker, style);
+        }
+        self.list_depth -= 1;
+        self.end_block();
+    }
+
+    /// Renders `item` after `marker`, indenting its other lines to the marker's width.
+    fn render_list_item(&mut self, item: &[Block], marker: String, style: Style) {
+        self.start_block();
+        let marker = Span::styled(marker, Style::default().fg(Color::Cyan));
+        let width = marker.width();
+        self.push_marker(marker);
+
+        self.indent.push(Span::raw(" ".repeat(width)));
+        self.render_blocks(item, style);
+        self.flush_line();
+        self.indent.pop();
+    }
+
+    fn render_table(&mut self, rows: &[TableRow], style: Style) {
+        self.flush_line();
+        let cells: Vec<Vec<Vec<Span<'static>>>> = rows
+            .iter()
+            .map(|row| {
+                row.cells
+                    .iter()
+                    .map(|cell| {
+                        let cell_style = if cell.header { style.add_modifier(Modifier::BOLD) } else { style };
+                        self.inline_spans(&cell.content, cell_style)
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+        let widths: Vec<usize> = (0..columns)
+            .map(|c| {
+                cells
+                    .iter()
+                    .filter_map(|row| row.get(c))
+                    .map(|cell| spans_width(cell))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+
+        let border = Style::default().fg(Color::DarkGray);
+        self.push_table_rule(&widths, ['┌', '┬', '┐'], border);
+        for (i, (row, row_cells)) in rows.iter().zip(cells).enumerate() {
+            let mut spans = self.indent.clone();
+            spans.push(Span::styled("│", border));
+            let mut row_cells = row_cells.into_iter();
+            for width in &widths {
+                let cell = row_cells.next().unwrap_or_default();
+                let padding = width - spans_width(&cell) + 1;
+                spans.push(Span::raw(" "));
+                spans.extend(cell);
+                spans.push(Span::raw(" ".repeat(padding)));
+                spans.push(Span::styled("│", border));
+            }
+            self.lines.push(clip_spans(spans, self.width));
+
+            // rows made only of <th> cells are underlined by a rule
+            if row.cells.iter().all(|cell| cell.header) && i + 1 < rows.len() {
+                self.push_table_rule(&widths, ['├', '┼', '┤'], border);
+            }
+        }
+        self.push_table_rule(&widths, ['└', '┴', '┘'], border);
+        self.end_block();
+    }
+
+    fn push_table_rule(&mut self, widths: &[usize], [left, middle, right]: [char; 3], border: Style) {
+        let segments: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+        let rule = format!("{}{}{}", left, segments.join(&middle.to_string()), right);
+        let mut spans = self.indent.clone();
+        spans.push(Span::styled(rule, border));
+        self.lines.push(clip_spans(spans, self.width));
+    }
+
+    /// Renders `inlines` on their own as one line, e.g. for a table cell.
+    fn inline_spans(&mut self, inlines: &[Inline], style: Style) -> Vec<Span<'static>> {
+        // cells are as wide as their text, the table is clipped as a whole
+        let mut cell = Renderer {
+            links: std::mem::take(&mut self.links),
+            ..Renderer::new(usize::MAX)
+        };
+        cell.render_inlines(inlines, style);
+        cell.flush_line();
+        self.links = cell.links;
+
+        let mut spans = Vec::new();
+        for line in cell.lines.into_iter().filter(|l| !l.spans.is_empty()) {
+            if !spans.is_empty() {
+                spans.push(Span::raw(" "));
+            }
+            spans.extend(line.spans);
+        }
+        spans
+    }
+
+    fn link_number(&mut self, href: &str) -> usize {
+        match self.links.iter().position(|link| link == href) {
+            Some(i) => i + 1,
+            None => {
+                self.links.push(href.to_string());
+                self.links.len()
+            }
+        }
+    }
+
+    /// Drops trailing blank lines and appends the link footnotes.
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush_line();
+        while self.lines.last().is_some_and(|l| l.spans.is_empty()) {
+            self.lines.pop();
+        }
+        if !self.links.is_empty() {
+            self.lines.push(Line::default());
+            for (i, link) in self.links.iter().enumerate() {
+                let number = Span::styled(format!("[{}] ", i + 1), Style::default().fg(Color::DarkGray));
+                // long urls continue under the url rather than the number
+                let hanging = [Span::raw(" ".repeat(number.width()))];
+                let link = Span::styled(link.clone(), Style::default().fg(Color::Blue));
+                self.lines.extend(wrap_spans(vec![number], vec![link], &hanging, self.width));
+            }
+        }
+        self.lines
+    }
+}
+
+/// Word-wraps `text` to `width` columns. The first line starts with `first_prefix`
+/// and the others with `prefix`; words longer than a line are broken anywhere.
+fn wrap_spans(
+    first_prefix: Vec<Span<'static>>,
+    text: Vec<Span<'static>>,
+    prefix: &[Span<'static>],
+    width: usize,
+) -> Vec<Line<'static>> {
+    let mut wrapper = Wrapper {
+        lines: Vec::new(),
+        used: spans_width(&first_prefix),
+        line: first_prefix,
+        has_text: false,
+        prefix,
+        width,
+    };
+
+    // a word may span several styles, e.g. `10` followed by a superscript
+    let mut word: Vec<Span<'static>> = Vec::new();
+    let mut space: Option<Span<'static>> = None;
+    for span in text {
+        let mut rest = span.content.as_ref();
+        while !rest.is_empty() {
+            let is_space = rest.starts_with(' ');
+            let end = rest.find(|c: char| (c == ' ') != is_space).unwrap_or(rest.len());
+            let piece = Span::styled(rest[..end].to_string(), span.style);
+            rest = &rest[end..];
+
+            if is_space {
+                wrapper.push_word(space.take(), std::mem::take(&mut word));
+                space = Some(piece);
+            } else {
+                word.push(piece);
+            }
+        }
+    }
+    // trailing spaces are dropped
+    wrapper.push_word(space, word);
+
+    wrapper.lines.push(Line::from(wrapper.line));
+    wrapper.lines
+}
+
+struct Wrapper<'a> {
+    lines: Vec<Line<'static>>,
+    line: Vec<Span<'static>>,
+    /// Columns taken on `line`, prefix included.
+    used: usize,
+    /// Whether `line` has anything besides its prefix.
+    has_text: bool,
+    prefix: &'a [Span<'static>],
+    width: usize,
+}
+
+impl Wrapper<'_> {
+    fn new_line(&mut self) {
+        let line = std::mem::replace(&mut self.line, self.prefix.to_vec());
+        self.lines.push(Line::from(line));
+        self.used = spans_width(self.prefix);
+        self.has_text = false;
+    }
+
+    /// Places `word` after `space`, which is dropped at the start of a line.
+    fn push_word(&mut self, space: Option<Span<'static>>, word: Vec<Span<'static>>) {
+        if word.is_empty() {
+            return;
+        }
+        let space_width = space.as_ref().map_or(0, Span::width);
+        if self.has_text && self.used + space_width + spans_width(&word) > self.width {
+            self.new_line();
+        }
+        if self.has_text
+            && let Some(space) = space
+        {
+            self.used += space.width();
+            self.line.push(space);
+        }
+
+        for piece in word {
+            if self.used + piece.width() <= self.width {
+                self.used += piece.width();
+                self.line.push(piece);
+                self.has_text = true;
+                continue;
+            }
+            let mut chunk = String::new();
+            for c in piece.content.chars() {
+                let char_width = c.width().unwrap_or(0);
+                if self.has_text && self.used + char_width > self.width {
+                    if !chunk.is_empty() {
+                        self.line.push(Span::styled(std::mem::take(&mut chunk), piece.style));
+                    }
+                    self.new_line();
+                }
+                chunk.push(c);
+                self.used += char_width;
+                self.has_text = true;
+            }
+            if !chunk.is_empty() {
+                self.line.push(Span::styled(chunk, piece.style));
+            }
+        }
+    }
+}
+
+/// Cuts `spans` to `width` columns, ending the line with a marker when anything was cut.
+fn clip_spans(spans: Vec<Span<'static>>, width: usize) -> Line<'static> {
+    if spans_width(&spans) <= width {
+        return Line::from(spans);
+    }
+    let marker = Span::styled(CLIP_MARKER, Style::default().fg(Color::DarkGray));
+    let mut available = width.saturating_sub(marker.width());
+    let mut clipped = Vec::new();
+    for span in spans {
+        if span.width() <= available {
+            available -= span.width();
+            clipped.push(span);
+            continue;
+        }
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let char_width = c.width().unwrap_or(0);
+            if char_width > available {
+                break;
+            }
+            available -= char_width;
+            content.push(c);
+        }
+        clipped.push(Span::styled(content, span.style));
+        break;
+    }
+    clipped.push(marker);
+    Line::from(clipped)
+}
+
+fn marks_style(marks: Marks, style: Style) -> Style {
+    let mut style = style;
+    if marks.bold {
+        style = style.add_modifier(Modifier::BOLD);
+    }
+    if marks.italic {
+        style = style.add_modifier(Modifier::ITALIC);
+    }
+    if marks.underline {
+        style = style.add_modifier(Modifier::UNDERLINED);
+    }
+    if marks.strike {
+        style = style.add_modifier(Modifier::CROSSED_OUT);
+    }
+    if marks.code {
+        style = style.fg(Color::LightYellow).bg(Color::DarkGray);
+    }
+    style
+}
+
+/// A span of a `<pre>` line, which only holds text.
+fn code_span(inline: &Inline, style: Style) -> Option<Span<'static>> {
+    match inline {
+        Inline::Text(text, marks) => Some(Span::styled(text.clone(), marks_style(*marks, style))),
+        Inline::Superscript(text, marks) => {
+            Some(Span::styled(shift_text(text, superscript, '^'), marks_style(*marks, style)))
+        }
+        Inline::Subscript(text, marks) => {
+            Some(Span::styled(shift_text(text, subscript, '_'), marks_style(*marks, style)))
+        }
+        _ => None,
+    }
+}
+
+fn spans_width(spans: &[Span]) -> usize {
+    spans.iter().map(Span::width).sum()
+}
+
+/// Maps every char of `text` with `shift`, e.g. "10" -> "¹⁰". Text with a char
+/// that has no such form is written as `^(x+y)` / `_(x+y)` instead.
+fn shift_text(text: &str, shift: fn(char) -> Option<char>, marker: char) -> String {
+    match text.chars().map(shift).collect::<Option<String>>() {
+        Some(shifted) => shifted,
+        None if text.chars().count() == 1 => format!("{}{}", marker, text),
+        None => format!("{}({})", marker, text),
+    }
+}
+
+/// Turns the `^5` and `^(n-1)` notation of plain text back into superscripts.
+fn shift_notation(text: &str) -> String {
+    let mut shifted = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(marker) = rest.find('^') {
+        shifted.push_str(&rest[..marker]);
+        rest = &rest[marker + 1..];
+        let (exponent, after) = match rest.strip_prefix('(').and_then(|r| r.split_once(')')) {
+            Some((inner, after)) => (inner, after),
+            None => {
+                let end = rest.find(|c: char| !c.is_alphanumeric()).unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            }
+        };
+        shifted.push_str(&shift_text(exponent, superscript, '^'));
+        rest = after;
+    }
+    shifted.push_str(rest);
+    shifted
+}
+
+fn superscript(c: char) -> Option<char> {
+    let shifted = match c {
+        '0' => '⁰',
+        '1' => '¹',
+        '2' => '²',
+        '3' => '³',
+        '4' => '⁴',
+        '5' => '⁵',
+        '6' => '⁶',
+        '7' => '⁷',
+        '8' => '⁸',
+        '9' => '⁹',
+        '+' => '⁺',
+        '-' => '⁻',
+        '=' => '⁼',
+        '(' => '⁽',
+        ')' => '⁾',
+        'a' => 'ᵃ',
+        'b' => 'ᵇ',
+        'c' => 'ᶜ',
+        'd' => 'ᵈ',
+        'e' => 'ᵉ',
+        'f' => 'ᶠ',
+        'g' => 'ᵍ',
+        'h' => 'ʰ',
+        'i' => 'ⁱ',
+        'j' => 'ʲ',
+        'k' => 'ᵏ',
+        'l' => 'ˡ',
+        'm' => 'ᵐ',
+        'n' => 'ⁿ',
+        'o' => 'ᵒ',
+        'p' => 'ᵖ',
+        'r' => 'ʳ',
+        's' => 'ˢ',
+        't' => 'ᵗ',
+        'u' => 'ᵘ',
+        'v' => 'ᵛ',
+        'w' => 'ʷ',
+        'x' => 'ˣ',
+        'y' => 'ʸ',
+        'z' => 'ᶻ',
+        _ => return None,
+    };
+    Some(shifted)
+}
+
+fn subscript(c: char) -> Option<char> {
+    let shifted = match c {
+        '0' => '₀',
+        '1' => '₁',
+        '2' => '₂',
+        '3' => '₃',
+        '4' => '₄',
+        '5' => '₅',
+        '6' => '₆',
+        '7' => '₇',
+        '8' => '₈',
+        '9' => '₉',
+        '+' => '₊',
+        '-' => '₋',
+        '=' => '₌',
+        '(' => '₍',
+        ')' => '₎',
+        'a' => 'ₐ',
+        'e' => 'ₑ',
+        'h' => 'ₕ',
+        'i' => 'ᵢ',
+        'j' => 'ⱼ',
+        'k' => 'ₖ',
+        'l' => 'ₗ',
+        'm' => 'ₘ',
+        'n' => 'ₙ',
+        'o' => 'ₒ',
+        'p' => 'ₚ',
+        'r' => 'ᵣ',
+        's' => 'ₛ',
+        't' => 'ₜ',
+        'u' => 'ᵤ',
+        'v' => 'ᵥ',
+        'x' => 'ₓ',
+        _ => return None,
+    };
+    Some(shifted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The text of the lines, without their styles.
+    fn layout(html: &str, width: usize) -> Vec<String> {
+        parse_html_to_lines(html, width)
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn wraps_at_word_boundaries() {
+        assert_eq!(
+            layout("<p>Return the indices of the two numbers.</p>", 16),
+            ["Return the", "indices of the", "two numbers."]
+        );
+    }
+
+    #[test]
+    fn breaks_words_longer_than_a_line() {
+        assert_eq!(layout("<p>abcdefghij</p>", 4), ["abcd", "efgh", "ij"]);
+    }
+
+    #[test]
+    fn hangs_list_items_under_their_marker() {
+        assert_eq!(
+            layout("<ul><li>first item that wraps</li><li>second</li></ul>", 14),
+            ["• first item", "  that wraps", "• second"]
+        );
+    }
+
+    #[test]
+    fn indents_nested_lists() {
+        assert_eq!(
+            layout("<ul><li>outer<ul><li>inner</li></ul></li></ul>", 40),
+            ["• outer", "  ◦ inner"]
+        );
+    }
+
+    #[test]
+    fn aligns_ordered_list_numbers() {
+        let items = "<li>x</li>".repeat(10);
+        let lines = layout(&format!("<ol start=\"1\">{}</ol>", items), 40);
+        assert_eq!(lines[0], " 1. x");
+        assert_eq!(lines[9], "10. x");
+    }
+
+    #[test]
+    fn numbers_from_the_start_attribute() {
+        assert_eq!(layout("<ol start=\"3\"><li>a</li><li>b</li></ol>", 40), ["3. a", "4. b"]);
+    }
+
+    #[test]
+    fn survives_a_huge_start() {
+        let lines = layout(&format!("<ol start=\"{}\"><li>a</li><li>b</li></ol>", i64::MAX), 40);
+        assert_eq!(lines, [format!("{}. a", i64::MAX), format!("{}. b", i64::MAX)]);
+    }
+
+    #[test]
+    fn keeps_code_lines_and_clips_them() {
+        assert_eq!(
+            layout("<pre>Input: nums = [2,7,11,15]\n  target = 9</pre>", 12),
+            ["Input: nums…", "  target = 9"]
+        );
+    }
+
+    #[test]
+    fn draws_tables() {
+        let html = "<table><tr><th>n</th><th>answer</th></tr><tr><td>10</td><td>4</td></tr></table>";
+        assert_eq!(
+            layout(html, 40),
+            [
+                "┌────┬────────┐",
+                "│ n  │ answer │",
+                "├────┼────────┤",
+                "│ 10 │ 4      │",
+                "└────┴────────┘",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_links_at_the_end() {
+        assert_eq!(
+            layout("<p>See <a href=\"https://example.com\">this</a>.</p>", 40),
+            ["See this[1].", "", "[1] https://example.com"]
+        );
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::{HashMap, HashSet};
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+
+use crate::types::Question;
+use crate::utils::complexity::suggested_complexity;
+use crate::utils::parse_html::{limits_to_lines, parse_html_to_lines};
+
+/// A previewed question, laid out.
+pub struct Preview {
+    pub statement: Vec<Line<'static>>,
+    /// The constraints panel, empty when the statement has none.
+    pub limits: Vec<Line<'static>>,
+}
+
+/// Laid-out statements of the previewed questions.
+///
+/// Parsing and wrapping a statement on every frame would be wasted work, so the
+/// lines are kept until the preview changes width, the questions are reloaded
+/// or the question leaves the window around the cursor.
+#[derive(Default)]
+pub struct PreviewCache {
+    width: u16,
+    previews: HashMap<String, Preview>,
+}
+
+impl PreviewCache {
+    /// The preview of `question` laid out for `width` columns.
+    pub fn preview(&mut self, question: &Question, width: u16) -> &Preview {
+        if width != self.width {
+            self.previews.clear();
+            self.width = width;
+        }
+        self.previews
+            .entry(question.title_slug.clone())
+            .or_insert_with(|| build_preview(question, width))
+    }
+
+    /// Drops the previews of questions other than `title_slugs`.
+    pub fn retain<'a>(&mut self, title_slugs: impl IntoIterator<Item = &'a str>) {
+        let keep: HashSet<&str> = title_slugs.into_iter().collect();
+        self.previews.retain(|slug, _| keep.contains(slug.as_str()));
+    }
+
+    pub fn clear(&mut self) {
+        self.previews.clear();
+    }
+}
+
+fn build_preview(question: &Question, width: u16) -> Preview {
+    let limits = question.limits();
+    let mut limit_lines = limits_to_lines(limits);
+    if let Some(complexity) = suggested_complexity(limits) {
+        limit_lines.push(Line::from(vec![
+            Span::styled("Aim for ", Style::default().fg(Color::DarkGray)),
+            Span::styled(complexity, Style::default().fg(Color::Cyan)),
+        ]));
+    }
+    Preview {
+        statement: parse_html_to_lines(&question.content, width as usize),
+        limits: limit_lines,
+    }
+}
@@ -0,0 +1,217 @@
+use std::io::Write;
+use std::str::FromStr;
+
+use serde::{Serialize, Serializer};
+
+use crate::types::{Progress, ProgressStatus, Question, QuestionSource, SimilarQuestion, Topic};
+use crate::utils::serde_json_string;
+
+/// File formats written by `zuko export`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// An array of rows; can be read back with `zuko import`.
+    Json,
+    /// The same rows with list columns as JSON; can be read back with `zuko import`.
+    Csv,
+    /// A table for sharing and reports, without statements.
+    Markdown,
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(ExportFormat::Json),
+            "csv" => Ok(ExportFormat::Csv),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            _ => Err(format!("Unsupported format '{}', expected json, csv or md", s)),
+        }
+    }
+}
+
+/// A question with the user's progress on it. The question fields match
+/// [`Question`], so importing an export restores the questions as they were,
+/// along with the progress on them.
+#[derive(Serialize)]
+pub struct ExportRow<'a> {
+    title: &'a str,
+    title_slug: &'a str,
+    content: &'a str,
+    difficulty: Option<&'a str>,
+    #[serde(serialize_with = "json_column")]
+    topic: &'a Option<Vec<Topic>>,
+    #[serde(serialize_with = "json_column")]
+    hints: &'a Option<Vec<String>>,
+    #[serde(serialize_with = "json_column")]
+    example_testcase_list: &'a Option<Vec<String>>,
+    #[serde(serialize_with = "json_column")]
+    similar_question_list: &'a Option<Vec<SimilarQuestion>>,
+    #[serde(serialize_with = "json_column")]
+    next_challenges: &'a Option<Vec<String>>,
+    source: &'a QuestionSource,
+    status: ProgressStatus,
+    bookmarked: bool,
+    last_solved_at: Option<&'a str>,
+}
+
+impl<'a> ExportRow<'a> {
+    pub fn new(question: &'a Question, progress: Option<&'a Progress>) -> Self {
+        ExportRow {
+            title: &question.title,
+            title_slug: &question.title_slug,
+            content: &question.content,
+            difficulty: question.difficulty.as_deref(),
+            topic: &question.topic,
+            hints: &question.hints,
+            example_testcase_list: &question.example_testcase_list,
+            similar_question_list: &question.similar_question_list,
+            next_challenges: &question.next_challenges,
+            source: &question.source,
+            status: progress.map(|p| p.status).unwrap_or_default(),
+            bookmarked: progress.is_some_and(|p| p.bookmarked),
+            last_solved_at: progress.and_then(|p| p.last_solved_at.as_deref()),
+        }
+    }
+
+    pub fn status(&self) -> ProgressStatus {
+        self.status
+    }
+}
+
+fn json_column<S, T>(value: &&Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    serde_json_string::serialize(value, serializer)
+}
+
+pub fn write_export(
+    rows: &[ExportRow],
+    format: ExportFormat,
+    mut writer: impl Write,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match format {
+        ExportFormat::Json => {
+            serde_json::to_writer_pretty(&mut writer, rows)?;
+            writeln!(writer)?;
+        }
+        ExportFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            for row in rows {
+                csv_writer.serialize(row)?;
+            }
+            csv_writer.flush()?;
+        }
+        ExportFormat::Markdown => write_markdown_table(rows, &mut writer)?,
+    }
+    Ok(())
+}
+
+fn write_markdown_table(rows: &[ExportRow], writer: &mut impl Write) -> std::io::Result<()> {
+    writeln!(writer, "| # | Title | Difficulty | Topics | Status | Bookmarked | Last solved |")?;
+    writeln!(writer, "|---|-------|------------|--------|--------|------------|-------------|")?;
+    for (i, row) in rows.iter().enumerate() {
+        let topics = row
+            .topic
+            .iter()
+            .flatten()
+            .map(|t| t.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+        let status = match row.status {
+            ProgressStatus::Todo => "Todo",
+            ProgressStatus::Attempted => "Attempted",
+            ProgressStatus::Solved => "Solved",
+        };
+        let last_solved = row
+            .last_solved_at
+            .and_then(|d| chrono::DateTime::parse_from_rfc3339(d).ok())
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+
+        writeln!(
+            writer,
+            "| {} | {} (`{}`) | {} | {} | {} | {} | {} |",
+            i + 1,
+            escape_cell(row.title),
+            row.title_slug,
+            row.difficulty.map(capitalize).unwrap_or_default(),
+            escape_cell(&topics),
+            status,
+            if row.bookmarked { "★" } else { "" },
+            last_solved
+        )?;
+    }
+    Ok(())
+}
+
+/// Keeps `|` and line breaks from ending a Markdown table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// "MEDIUM" -> "Medium"
+fn capitalize(text: &str) -> String {
+    let lower = text.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::problem_import::{read_problem_file, ImportFormat};
+
+    fn question() -> Question {
+        Question {
+            title: "Two Sum".to_string(),
+            title_slug: "two-sum".to_string(),
+            content: "<p>Find two numbers, \"quoted\", that add up to <code>target</code>.</p>\n".to_string(),
+            difficulty: Some("EASY".to_string()),
+            topic: Some(vec![Topic { name: "Array".to_string(), slug: "array".to_string() }]),
+            hints: Some(vec!["Use a map, or two pointers.".to_string()]),
+            example_testcase_list: Some(vec!["[2,7,11,15]\n9".to_string()]),
+            similar_question_list: None,
+            next_challenges: Some(Vec::new()),
+            source: QuestionSource::Pack("graph-drills".to_string()),
+            parsed: Default::default(),
+        }
+    }
+
+    #[test]
+    fn export_round_trips_through_import() {
+        let question = question();
+        let progress = Progress {
+            title_slug: question.title_slug.clone(),
+            status: ProgressStatus::Solved,
+            bookmarked: true,
+            last_solved_at: Some("2026-10-01T12:00:00+00:00".to_string()),
+        };
+        let rows = [ExportRow::new(&question, Some(&progress))];
+
+        for (format, import_format, suffix) in [
+            (ExportFormat::Json, ImportFormat::Json, ".json"),
+            (ExportFormat::Csv, ImportFormat::Csv, ".csv"),
+        ] {
+            let file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+            write_export(&rows, format, file.as_file()).unwrap();
+
+            let imported = read_problem_file(file.path(), import_format).unwrap();
+            assert_eq!(imported.len(), 1);
+            assert_eq!(
+                serde_json::to_value(&imported[0].question).unwrap(),
+                serde_json::to_value(&question).unwrap(),
+                "{suffix}"
+            );
+            let restored = &imported[0].progress;
+            assert_eq!(restored.status, Some(ProgressStatus::Solved), "{suffix}");
+            assert_eq!(restored.bookmarked, Some(true), "{suffix}");
+            assert_eq!(restored.last_solved_at, progress.last_solved_at, "{suffix}");
+        }
+    }
+}
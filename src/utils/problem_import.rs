@@ -0,0 +1,367 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use pulldown_cmark::{html, Parser};
+use serde::Deserialize;
+
+use crate::types::{ProgressStatus, Question, QuestionSource, Topic};
+
+/// File formats accepted by `zuko import`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// One question per row, with the `Question` field names as headers and
+    /// list columns (`topic`, `hints`, ...) as JSON.
+    Csv,
+    /// A `Question` object or an array of them.
+    Json,
+    /// One question per file: front matter followed by the statement in Markdown.
+    Markdown,
+}
+
+impl ImportFormat {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| ext.parse().ok())
+    }
+}
+
+impl FromStr for ImportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ImportFormat::Csv),
+            "json" => Ok(ImportFormat::Json),
+            "md" | "markdown" => Ok(ImportFormat::Markdown),
+            _ => Err(format!("Unsupported format '{}', expected csv, json or md", s)),
+        }
+    }
+}
+
+/// A question read by [`read_problem_file`].
+pub struct ProblemRow {
+    pub question: Question,
+    pub progress: ImportedProgress,
+}
+
+/// The progress columns written by `zuko export`; files without them leave
+/// everything `None`.
+#[derive(Debug, Default, Deserialize)]
+pub struct ImportedProgress {
+    #[serde(default)]
+    pub status: Option<ProgressStatus>,
+    #[serde(default)]
+    pub bookmarked: Option<bool>,
+    #[serde(default)]
+    pub last_solved_at: Option<String>,
+}
+
+impl ImportedProgress {
+    /// Whether there is anything to restore.
+    pub fn is_set(&self) -> bool {
+        self.status.is_some_and(|status| status != ProgressStatus::Todo) || self.bookmarked == Some(true)
+    }
+}
+
+/// Reads every question in `path`. The rows still need [`validate_questions`].
+pub fn read_problem_file(path: &Path, format: ImportFormat) -> Result<Vec<ProblemRow>, Box<dyn std::error::Error>> {
+    match format {
+        ImportFormat::Csv => {
+            let mut reader = csv::Reader::from_path(path)?;
+            let headers = reader.headers()?.clone();
+            let mut rows = Vec::new();
+            for (i, record) in reader.records().enumerate() {
+                let row = record.and_then(|record| {
+                    Ok(ProblemRow {
+                        question: record.deserialize(Some(&headers))?,
+                        progress: record.deserialize(Some(&headers))?,
+                    })
+                });
+                // row 1 is the header
+                rows.push(row.map_err(|e| format!("row {}: {}", i + 2, e))?);
+            }
+            Ok(rows)
+        }
+        ImportFormat::Json => {
+            let text = fs::read_to_string(path)?;
+            match serde_json::from_str::<serde_json::Value>(&text)? {
+                serde_json::Value::Array(items) => items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(i, item)| json_row(item).map_err(|e| format!("item {}: {}", i + 1, e).into()))
+                    .collect(),
+                item => Ok(vec![json_row(item)?]),
+            }
+        }
+        ImportFormat::Markdown => Ok(vec![ProblemRow {
+            question: parse_markdown_question(&fs::read_to_string(path)?)?,
+            progress: ImportedProgress::default(),
+        }]),
+    }
+}
+
+fn json_row(item: serde_json::Value) -> Result<ProblemRow, serde_json::Error> {
+    Ok(ProblemRow {
+        progress: serde_json::from_value(item.clone())?,
+        question: serde_json::from_value(item)?,
+    })
+}
+
+/// Parses a Markdown problem such as:
+///
+/// ```text
+/// ---
+/// title: Merge Intervals
+/// difficulty: Medium
+/// topics: [Array, Sorting]
+/// hints:
+///   - Sort by start first.
+/// ---
+/// Given an array of `intervals`...
+/// ```
+///
+/// `title_slug` defaults to a slug of the title; `examples` and `next_challenges`
+/// are lists like `hints`. The body becomes the HTML `content`.
+pub fn parse_markdown_question(text: &str) -> Result<Question, Box<dyn std::error::Error>> {
+    let text = text.trim_start_matches('\u{feff}');
+    let rest = text
+        .strip_prefix("---")
+        .ok_or("missing front matter, the file must start with ---")?;
+    let (front_matter, body) = rest
+        .split_once("\n---")
+        .ok_or("unterminated front matter, expected a closing ---")?;
+    // drop the rest of the closing `---` line
+    let body = body.split_once('\n').map_or("", |(_, body)| body);
+
+    let mut question = Question {
+        title: String::new(),
+        title_slug: String::new(),
+        content: markdown_to_html(body),
+        difficulty: None,
+        topic: None,
+        hints: None,
+        example_testcase_list: None,
+        similar_question_list: None,
+        next_challenges: None,
+        source: QuestionSource::Imported,
+        parsed: Default::default(),
+    };
+
+    for (key, value) in parse_front_matter(front_matter)? {
+        match (key.as_str(), value) {
+            ("title", FrontMatterValue::Text(v)) => question.title = v,
+            ("title_slug" | "slug", FrontMatterValue::Text(v)) => question.title_slug = v,
+            ("difficulty", FrontMatterValue::Text(v)) => question.difficulty = Some(v),
+            ("topics" | "topic", value) => {
+                question.topic = Some(
+                    value
+                        .into_list()
+                        .into_iter()
+                        .map(|name| Topic { slug: slugify(&name), name })
+                        .collect(),
+                )
+            }
+            ("hints", value) => question.hints = Some(value.into_list()),
+            ("examples" | "example_testcase_list", value) => {
+                question.example_testcase_list = Some(value.into_list())
+            }
+            ("next_challenges", value) => question.next_challenges = Some(value.into_list()),
+            (key, _) => return Err(format!("unknown front matter key '{}'", key).into()),
+        }
+    }
+
+    if question.title_slug.is_empty() {
+        question.title_slug = slugify(&question.title);
+    }
+    Ok(question)
+}
+
+#[derive(Debug, PartialEq)]
+enum FrontMatterValue {
+    Text(String),
+    List(Vec<String>),
+}
+
+impl FrontMatterValue {
+    fn into_list(self) -> Vec<String> {
+        match self {
+            FrontMatterValue::Text(v) => vec![v],
+            FrontMatterValue::List(items) => items,
+        }
+    }
+}
+
+/// Reads the small YAML subset used in problem files: `key: value`, inline
+/// `[a, b]` lists and block lists of `- item` lines.
+fn parse_front_matter(text: &str) -> Result<Vec<(String, FrontMatterValue)>, String> {
+    let mut entries: Vec<(String, FrontMatterValue)> = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        if let Some(item) = trimmed.strip_prefix("- ") {
+            match entries.last_mut() {
+                Some((_, FrontMatterValue::List(items))) => items.push(unquote(item)),
+                _ => return Err(format!("front matter line {}: list item without a key", i + 1)),
+            }
+            continue;
+        }
+
+        let (key, value) = trimmed
+            .split_once(':')
+            .ok_or_else(|| format!("front matter line {}: expected `key: value`", i + 1))?;
+        let value = value.trim();
+        let value = if value.is_empty() {
+            // a block list follows
+            FrontMatterValue::List(Vec::new())
+        } else if let Some(inner) = value.strip_prefix('[').and_then(|v| v.strip_suffix(']')) {
+            FrontMatterValue::List(
+                inner
+                    .split(',')
+                    .map(unquote)
+                    .filter(|item| !item.is_empty())
+                    .collect(),
+            )
+        } else {
+            FrontMatterValue::Text(unquote(value))
+        };
+        entries.push((key.trim().to_lowercase(), value));
+    }
+    Ok(entries)
+}
+
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    value
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+        .unwrap_or(value)
+        .to_string()
+}
+
+fn markdown_to_html(markdown: &str) -> String {
+    let mut out = String::new();
+    html::push_html(&mut out, Parser::new(markdown));
+    out
+}
+
+/// Lowercases `text` and joins its words with hyphens, e.g. "Two Sum II" -> "two-sum-ii".
+pub fn slugify(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Checks every question and normalizes difficulties to the database's `EASY`
+/// form, returning one message per problem found.
+pub fn validate_questions(questions: &mut [Question], reserved_slugs: &HashSet<String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut seen = HashSet::new();
+
+    for (i, question) in questions.iter_mut().enumerate() {
+        let label = if question.title_slug.is_empty() {
+            format!("#{}", i + 1)
+        } else {
+            format!("#{} ({})", i + 1, question.title_slug)
+        };
+        let mut fail = |message: &str| errors.push(format!("{}: {}", label, message));
+
+        if question.title.trim().is_empty() {
+            fail("title is empty");
+        }
+        if question.content.trim().is_empty() {
+            fail("content is empty");
+        }
+        if question.title_slug.is_empty() {
+            fail("title_slug is empty");
+        } else if !question
+            .title_slug
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_')
+        {
+            fail("title_slug may only contain lowercase letters, digits, '-' and '_'");
+        } else if reserved_slugs.contains(&question.title_slug) {
+            fail("title_slug is already used by another question");
+        } else if !seen.insert(question.title_slug.clone()) {
+            fail("title_slug appears more than once in the file");
+        }
+
+        if let Some(difficulty) = &question.difficulty {
+            match difficulty.to_uppercase().as_str() {
+                d @ ("EASY" | "MEDIUM" | "HARD") => question.difficulty = Some(d.to_string()),
+                _ => fail("difficulty must be Easy, Medium or Hard"),
+            }
+        }
+        for topic in question.topic.iter().flatten() {
+            if topic.name.trim().is_empty() || topic.slug.trim().is_empty() {
+                fail("topics need a name and a slug");
+            }
+        }
+    }
+    errors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(value: &str) -> FrontMatterValue {
+        FrontMatterValue::Text(value.to_string())
+    }
+
+    fn list(items: &[&str]) -> FrontMatterValue {
+        FrontMatterValue::List(items.iter().map(|item| item.to_string()).collect())
+    }
+
+    #[test]
+    fn parses_values_and_lists() {
+        let front_matter = "\n# a comment\nTitle: \"Merge: Intervals\"\ndifficulty: Medium\n\
+            topics: [Array, 'Sorting', ]\nhints:\n  - Sort by start first.\n  - \"Then merge\"\n";
+        assert_eq!(
+            parse_front_matter(front_matter).unwrap(),
+            [
+                ("title".to_string(), text("Merge: Intervals")),
+                ("difficulty".to_string(), text("Medium")),
+                ("topics".to_string(), list(&["Array", "Sorting"])),
+                ("hints".to_string(), list(&["Sort by start first.", "Then merge"])),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_the_broken_line() {
+        assert_eq!(
+            parse_front_matter("title: A\n- stray").unwrap_err(),
+            "front matter line 2: list item without a key"
+        );
+        assert_eq!(
+            parse_front_matter("title: A\nno colon").unwrap_err(),
+            "front matter line 2: expected `key: value`"
+        );
+    }
+
+    #[test]
+    fn parses_a_markdown_question() {
+        let text = "---\ntitle: Two Sum II\ndifficulty: Easy\ntopics: [Two Pointers]\n---\n\
+            Find two numbers in `numbers`.\n";
+        let question = parse_markdown_question(text).unwrap();
+        assert_eq!(question.title_slug, "two-sum-ii");
+        assert_eq!(question.difficulty.as_deref(), Some("Easy"));
+        assert_eq!(question.topic.unwrap()[0].slug, "two-pointers");
+        assert_eq!(question.content, "<p>Find two numbers in <code>numbers</code>.</p>\n");
+        assert_eq!(question.source, QuestionSource::Imported);
+
+        assert!(parse_markdown_question("no front matter").is_err());
+        assert!(parse_markdown_question("---\ntitle: A\nbody").is_err());
+        assert!(parse_markdown_question("---\ncolour: red\n---\n").is_err());
+    }
+}
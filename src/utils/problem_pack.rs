@@ -0,0 +1,186 @@
+use std::collections::HashSet;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use serde::Deserialize;
+use tempfile::TempDir;
+
+use crate::types::{Question, Topic};
+use crate::utils::problem_import::{read_problem_file, validate_questions, ImportFormat};
+
+/// Name of the manifest at the root of every pack.
+pub const MANIFEST_FILE: &str = "pack.toml";
+
+/// `pack.toml`, e.g.
+///
+/// ```toml
+/// name = "graph-drills"
+/// version = "1.2.0"
+/// author = "Jane Doe"
+/// license = "CC-BY-4.0"
+/// description = "Forty BFS/DFS warm-ups"
+/// questions = "questions.json"   # or a directory of Markdown problems
+/// topics = "topics.json"         # optional
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    pub version: String,
+    pub author: String,
+    pub license: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Path of the questions, relative to the manifest: a file in any format
+    /// `zuko import` reads, or a directory of Markdown files.
+    #[serde(default = "default_questions_path")]
+    pub questions: String,
+    /// Path of a JSON array of topics, relative to the manifest.
+    #[serde(default)]
+    pub topics: Option<String>,
+}
+
+fn default_questions_path() -> String {
+    "questions.json".to_string()
+}
+
+/// A pack read from disk, ready to be validated and installed.
+pub struct ProblemPack {
+    pub manifest: PackManifest,
+    pub questions: Vec<Question>,
+    pub topics: Vec<Topic>,
+}
+
+/// Reads the pack at `path`, a directory or a `.tar.gz`/`.tgz` bundle whose
+/// manifest sits at the root or inside a single top-level directory.
+pub fn read_pack(path: &Path) -> Result<ProblemPack, Box<dyn std::error::Error>> {
+    if path.is_dir() {
+        return read_pack_dir(path);
+    }
+
+    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+    if !(name.ends_with(".tar.gz") || name.ends_with(".tgz")) {
+        return Err(format!("{} is neither a directory nor a .tar.gz bundle", path.display()).into());
+    }
+
+    // the extracted files only need to live until the pack is read
+    let dir = TempDir::new()?;
+    tar::Archive::new(GzDecoder::new(File::open(path)?)).unpack(dir.path())?;
+    read_pack_dir(&find_pack_root(dir.path())?)
+}
+
+fn find_pack_root(dir: &Path) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    if dir.join(MANIFEST_FILE).is_file() {
+        return Ok(dir.to_path_buf());
+    }
+    let entries: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .collect();
+    match entries.as_slice() {
+        [only] if only.join(MANIFEST_FILE).is_file() => Ok(only.clone()),
+        _ => Err(format!("the bundle has no {}", MANIFEST_FILE).into()),
+    }
+}
+
+fn read_pack_dir(dir: &Path) -> Result<ProblemPack, Box<dyn std::error::Error>> {
+    let manifest_path = dir.join(MANIFEST_FILE);
+    let manifest: PackManifest = toml::from_str(
+        &fs::read_to_string(&manifest_path)
+            .map_err(|e| format!("can't read {}: {}", manifest_path.display(), e))?,
+    )
+    .map_err(|e| format!("invalid {}: {}", MANIFEST_FILE, e))?;
+
+    let questions_path = dir.join(&manifest.questions);
+    let questions = if questions_path.is_dir() {
+        read_markdown_dir(&questions_path)?
+    } else {
+        let format = ImportFormat::from_path(&questions_path)
+            .ok_or_else(|| format!("can't tell the format of {}", manifest.questions))?;
+        read_problem_file(&questions_path, format)
+            .map_err(|e| format!("{}: {}", manifest.questions, e))?
+            .into_iter()
+            .map(|row| row.question)
+            .collect()
+    };
+
+    let topics = match &manifest.topics {
+        Some(topics) => serde_json::from_str(&fs::read_to_string(dir.join(topics))?)
+            .map_err(|e| format!("{}: {}", topics, e))?,
+        None => Vec::new(),
+    };
+
+    Ok(ProblemPack { manifest, questions, topics })
+}
+
+/// Reads every `.md` file in `dir`, in file name order.
+fn read_markdown_dir(dir: &Path) -> Result<Vec<Question>, Box<dyn std::error::Error>> {
+    let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| ImportFormat::from_path(path) == Some(ImportFormat::Markdown))
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .map(|path| {
+            read_problem_file(path, ImportFormat::Markdown)
+                .map(|mut rows| rows.remove(0).question)
+                .map_err(|e| format!("{}: {}", path.display(), e).into())
+        })
+        .collect()
+}
+
+/// Pack names end up in table names, so they're limited to lowercase letters,
+/// digits and single hyphens.
+pub fn is_valid_pack_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-')
+        && !name.contains("--")
+}
+
+/// Checks the manifest, questions and topics, returning one message per problem.
+/// Difficulties are normalized as for `zuko import`.
+pub fn validate_pack(pack: &mut ProblemPack, reserved_slugs: &HashSet<String>) -> Vec<String> {
+    let mut errors = Vec::new();
+    let manifest = &pack.manifest;
+
+    if !is_valid_pack_name(&manifest.name) {
+        errors.push(format!(
+            "{}: name '{}' may only contain lowercase letters, digits and single hyphens",
+            MANIFEST_FILE, manifest.name
+        ));
+    }
+    let is_version = manifest.version.split('.').count() == 3
+        && manifest
+            .version
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    if !is_version {
+        errors.push(format!(
+            "{}: version '{}' must look like MAJOR.MINOR.PATCH",
+            MANIFEST_FILE, manifest.version
+        ));
+    }
+    for (field, value) in [("author", &manifest.author), ("license", &manifest.license)] {
+        if value.trim().is_empty() {
+            errors.push(format!("{}: {} is empty", MANIFEST_FILE, field));
+        }
+    }
+
+    if pack.questions.is_empty() {
+        errors.push("the pack has no questions".to_string());
+    }
+    errors.extend(validate_questions(&mut pack.questions, reserved_slugs));
+
+    let mut topic_slugs = HashSet::new();
+    for topic in &pack.topics {
+        if topic.name.trim().is_empty() || topic.slug.trim().is_empty() {
+            errors.push("topics need a name and a slug".to_string());
+        } else if !topic_slugs.insert(topic.slug.as_str()) {
+            errors.push(format!("topic '{}' is listed more than once", topic.slug));
+        }
+    }
+    errors
+}
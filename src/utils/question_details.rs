@@ -0,0 +1,98 @@
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::db::zuko_cli::get_questions;
+use crate::types::Question;
+use crate::ui::event::AppEvent;
+
+/// How long a failed question is left alone before it is fetched again.
+const RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Full questions loaded on demand for the preview.
+///
+/// The question list only holds summaries; statements, hints and examples are
+/// fetched here in background tasks and kept for the rest of the session, so a
+/// question is read from the database at most once. Failed fetches are retried
+/// once the cursor is on them again after [`RETRY_DELAY`].
+#[derive(Default)]
+pub struct QuestionDetails {
+    cache: HashMap<String, Question>,
+    /// Slugs with a fetch in flight.
+    pending: HashSet<String>,
+    /// Slugs whose fetch failed, with the error to show instead of the preview
+    /// and when it happened.
+    errors: HashMap<String, (String, Instant)>,
+}
+
+impl QuestionDetails {
+    pub fn get(&self, title_slug: &str) -> Option<&Question> {
+        self.cache.get(title_slug)
+    }
+
+    pub fn error(&self, title_slug: &str) -> Option<&str> {
+        self.errors.get(title_slug).map(|(error, _)| error.as_str())
+    }
+
+    /// Starts fetching the questions in `title_slugs` that are neither cached,
+    /// in flight nor failed within the last [`RETRY_DELAY`]. The results come
+    /// back through `events`.
+    pub fn request<'a>(
+        &mut self,
+        title_slugs: impl IntoIterator<Item = &'a str>,
+        events: &UnboundedSender<AppEvent>,
+    ) {
+        let missing: Vec<String> = title_slugs
+            .into_iter()
+            .filter(|slug| {
+                !self.cache.contains_key(*slug)
+                    && !self.pending.contains(*slug)
+                    && self
+                        .errors
+                        .get(*slug)
+                        .is_none_or(|(_, failed_at)| failed_at.elapsed() >= RETRY_DELAY)
+            })
+            .map(str::to_string)
+            .collect();
+        if missing.is_empty() {
+            return;
+        }
+
+        self.pending.extend(missing.iter().cloned());
+        let events = events.clone();
+        tokio::spawn(async move {
+            let questions = get_questions(&missing).await.map_err(|e| e.to_string());
+            // the receiver is only gone once the UI has exited
+            let _ = events.send(AppEvent::Update(Box::new(move |app| {
+                app.details.store(missing, questions)
+            })));
+        });
+    }
+
+    fn store(&mut self, title_slugs: Vec<String>, questions: Result<Vec<Question>, String>) {
+        for slug in &title_slugs {
+            self.pending.remove(slug);
+        }
+        match questions {
+            Ok(questions) => {
+                for question in questions {
+                    self.errors.remove(&question.title_slug);
+                    self.cache.insert(question.title_slug.clone(), question);
+                }
+                // a slug the database no longer has would otherwise be requested forever
+                for slug in title_slugs {
+                    if !self.cache.contains_key(&slug) {
+                        self.errors.insert(slug, ("Question not found".to_string(), Instant::now()));
+                    }
+                }
+            }
+            Err(e) => {
+                let failed_at = Instant::now();
+                for slug in title_slugs {
+                    self.errors.insert(slug, (e.clone(), failed_at));
+                }
+            }
+        }
+    }
+}
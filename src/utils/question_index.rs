@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use crate::types::{DifficultyFilter, QuestionSource, QuestionSummary, SourceFilter, TopicMatch, TopicSelection};
+
+/// Positions of the loaded questions grouped by topic slug, difficulty and source,
+/// so the list filters never have to go back to the database.
+///
+/// Every position list is sorted ascending, which keeps merges linear.
+#[derive(Debug, Default)]
+pub struct QuestionIndex {
+    len: usize,
+    by_topic: HashMap<String, Vec<usize>>,
+    /// Keyed by the uppercase difficulty stored in the database, e.g. `EASY`.
+    by_difficulty: HashMap<String, Vec<usize>>,
+    by_source: HashMap<QuestionSource, Vec<usize>>,
+}
+
+impl QuestionIndex {
+    pub fn new(questions: &[QuestionSummary]) -> Self {
+        let mut index = QuestionIndex {
+            len: questions.len(),
+            ..Default::default()
+        };
+
+        for (i, question) in questions.iter().enumerate() {
+            for topic in question.topic.iter().flatten() {
+                let positions = index.by_topic.entry(topic.slug.clone()).or_default();
+                // a topic listed twice on one question must not duplicate the position
+                if positions.last() != Some(&i) {
+                    positions.push(i);
+                }
+            }
+            if let Some(difficulty) = &question.difficulty {
+                index
+                    .by_difficulty
+                    .entry(difficulty.to_uppercase())
+                    .or_default()
+                    .push(i);
+            }
+            index.by_source.entry(question.source.clone()).or_default().push(i);
+        }
+
+        index
+    }
+
+    /// Returns the sorted positions of the questions passing every filter.
+    pub fn filter(
+        &self,
+        topics: &TopicSelection,
+        difficulty: DifficultyFilter,
+        source: &SourceFilter,
+    ) -> Vec<usize> {
+        let mut positions = match topics.topics.as_slice() {
+            [] => (0..self.len).collect(),
+            selected => {
+                let mut lists = selected.iter().map(|t| self.topic_positions(&t.slug));
+                match topics.mode {
+                    TopicMatch::Any => lists.fold(Vec::new(), |acc, list| union(&acc, list)),
+                    TopicMatch::All => {
+                        let first = lists.next().unwrap_or_default().to_vec();
+                        lists.fold(first, |acc, list| intersect(&acc, list))
+                    }
+                }
+            }
+        };
+
+        if let DifficultyFilter::Specific(d) = difficulty {
+            let list = self.by_difficulty.get(&d.to_str().to_uppercase());
+            positions = intersect(&positions, list.map(Vec::as_slice).unwrap_or_default());
+        }
+        if let SourceFilter::Specific(s) = source {
+            let list = self.by_source.get(s);
+            positions = intersect(&positions, list.map(Vec::as_slice).unwrap_or_default());
+        }
+
+        positions
+    }
+
+    fn topic_positions(&self, slug: &str) -> &[usize] {
+        self.by_topic.get(slug).map(Vec::as_slice).unwrap_or_default()
+    }
+}
+
+fn intersect(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len().min(b.len()));
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => i += 1,
+            std::cmp::Ordering::Greater => j += 1,
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out
+}
+
+fn union(a: &[usize], b: &[usize]) -> Vec<usize> {
+    let (mut i, mut j) = (0, 0);
+    let mut out = Vec::with_capacity(a.len() + b.len());
+    while i < a.len() && j < b.len() {
+        match a[i].cmp(&b[j]) {
+            std::cmp::Ordering::Less => {
+                out.push(a[i]);
+                i += 1;
+            }
+            std::cmp::Ordering::Greater => {
+                out.push(b[j]);
+                j += 1;
+            }
+            std::cmp::Ordering::Equal => {
+                out.push(a[i]);
+                i += 1;
+                j += 1;
+            }
+        }
+    }
+    out.extend_from_slice(&a[i..]);
+    out.extend_from_slice(&b[j..]);
+    out
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+use std::thread;
+
+use crate::types::Progress;
+use crate::utils::fuzzy_matcher::{QueryMatcher, QuestionMatch, SearchItem, SearchQuery};
+
+/// Number of items matched between checks for a newer request.
+const CHUNK_SIZE: usize = 2048;
+
+struct SearchRequest {
+    generation: u64,
+    query: String,
+    items: Arc<Vec<SearchItem>>,
+    /// Sorted positions in `items` left by the topic and difficulty filters.
+    scope: Arc<Vec<usize>>,
+    progress: Arc<HashMap<String, Progress>>,
+}
+
+struct SearchResults {
+    generation: u64,
+    matches: Vec<QuestionMatch>,
+}
+
+/// The last completed search, reused as the candidate set when the next query narrows it.
+struct LastSearch {
+    items: Arc<Vec<SearchItem>>,
+    scope: Arc<Vec<usize>>,
+    query: SearchQuery,
+    progress: Arc<HashMap<String, Progress>>,
+    matched: Vec<usize>,
+}
+
+/// Runs question searches on a background thread so typing never waits on matching.
+///
+/// Each submitted query supersedes the previous one: stale searches are abandoned
+/// between chunks, and when a query only extends the last one (e.g. a character was
+/// appended) just the previous matches are re-scored instead of the whole list.
+pub struct SearchWorker {
+    requests: Sender<SearchRequest>,
+    results: Receiver<SearchResults>,
+    items: Arc<Vec<SearchItem>>,
+    generation: u64,
+    received_generation: u64,
+}
+
+impl SearchWorker {
+    pub fn new(items: Vec<SearchItem>) -> Self {
+        let (request_tx, request_rx) = mpsc::channel();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        thread::spawn(move || run_worker(request_rx, result_tx));
+
+        SearchWorker {
+            requests: request_tx,
+            results: result_rx,
+            items: Arc::new(items),
+            generation: 0,
+            received_generation: 0,
+        }
+    }
+
+    /// Replaces the searched items; later submissions search the new ones.
+    pub fn set_items(&mut self, items: Vec<SearchItem>) {
+        self.items = Arc::new(items);
+    }
+
+    /// Searches the items at the positions in `scope` for `query`. Narrowed queries
+    /// only reuse the last matches while `scope` and `progress` are the same `Arc`s.
+    pub fn submit(
+        &mut self,
+        query: &str,
+        scope: &Arc<Vec<usize>>,
+        progress: &Arc<HashMap<String, Progress>>,
+    ) {
+        self.generation += 1;
+        let request = SearchRequest {
+            generation: self.generation,
+            query: query.to_string(),
+            items: Arc::clone(&self.items),
+            scope: Arc::clone(scope),
+            progress: Arc::clone(progress),
+        };
+        // the worker only stops once this sender is dropped
+        let _ = self.requests.send(request);
+    }
+
+    /// Returns the results of the most recent submission if they have arrived.
+    pub fn try_recv(&mut self) -> Option<Vec<QuestionMatch>> {
+        let mut latest = None;
+        while let Ok(results) = self.results.try_recv() {
+            self.received_generation = results.generation;
+            if results.generation == self.generation {
+                latest = Some(results.matches);
+            }
+        }
+        latest
+    }
+
+    pub fn is_searching(&self) -> bool {
+        self.received_generation != self.generation
+    }
+}
+
+fn run_worker(requests: Receiver<SearchRequest>, results: Sender<SearchResults>) {
+    let mut last: Option<LastSearch> = None;
+    let mut pending: Option<SearchRequest> = None;
+
+    loop {
+        let mut request = match pending.take() {
+            Some(request) => request,
+            None => match requests.recv() {
+                Ok(request) => request,
+                Err(_) => return,
+            },
+        };
+        // skip straight to the newest query
+        while let Ok(newer) = requests.try_recv() {
+            request = newer;
+        }
+
+        let query = SearchQuery::parse(&request.query);
+        let candidates: Vec<usize> = match &last {
+            Some(prev)
+                if Arc::ptr_eq(&prev.items, &request.items)
+                    && Arc::ptr_eq(&prev.scope, &request.scope)
+                    && Arc::ptr_eq(&prev.progress, &request.progress)
+                    && query.narrows(&prev.query) =>
+            {
+                let mut matched = prev.matched.clone();
+                // rank ties by original order, same as a full search
+                matched.sort_unstable();
+                matched
+            }
+            _ => request.scope.to_vec(),
+        };
+
+        let mut matcher = QueryMatcher::new(query);
+        let mut matches = Vec::new();
+        let mut superseded = false;
+
+        for chunk in candidates.chunks(CHUNK_SIZE) {
+            match requests.try_recv() {
+                Ok(newer) => {
+                    pending = Some(newer);
+                    superseded = true;
+                    break;
+                }
+                Err(TryRecvError::Disconnected) => return,
+                Err(TryRecvError::Empty) => {}
+            }
+            matches.extend(chunk.iter().filter_map(|&i| {
+                let item = &request.items[i];
+                matcher.match_item(i, item, request.progress.get(&item.title_slug))
+            }));
+        }
+        if superseded {
+            continue;
+        }
+
+        // stable sort keeps the original order between equally ranked questions
+        matches.sort_by_key(|m| std::cmp::Reverse(m.score));
+
+        last = Some(LastSearch {
+            items: Arc::clone(&request.items),
+            scope: Arc::clone(&request.scope),
+            query: matcher.query().clone(),
+            progress: request.progress,
+            matched: matches.iter().map(|m| m.index).collect(),
+        });
+
+        if results
+            .send(SearchResults { generation: request.generation, matches })
+            .is_err()
+        {
+            return;
+        }
+    }
+}
@@ -1,8 +1,11 @@
-use serde::{self, de::Error, de::DeserializeOwned, Deserializer, Deserialize};
+use serde::{self, de::Error, de::DeserializeOwned, ser, Deserializer, Deserialize, Serialize, Serializer};
 use serde_json;
 
     // This generic function can deserialize any type T from a JSON string
     // that is stored in the database.
+    //
+    // Hand-written files (see `zuko import`) may also hold the list itself
+    // instead of a string, so both forms are accepted.
 pub fn deserialize<'de, D, T>(deserializer: D) -> Result<Option<Vec<T>>, D::Error>
 where
     D: Deserializer<'de>,
@@ -12,11 +15,11 @@ where
     // This resolves the lifetime conflict.
     T: DeserializeOwned,
 {
-    // Deserialize the field from the database row as an Option<String>
-    let opt_s = Option::<String>::deserialize(deserializer)?;
+    // Deserialize the field as an Option<Value>; database rows always give a string
+    let opt_value = Option::<serde_json::Value>::deserialize(deserializer)?;
 
-    match opt_s {
-        Some(s) => {
+    match opt_value {
+        Some(serde_json::Value::String(s)) => {
             // If the string from the DB is empty, treat it as no data.
             if s.is_empty() {
                 return Ok(None);
@@ -27,7 +30,25 @@ where
             // to be destroyed at the end of this function.
             serde_json::from_str(&s).map(Some).map_err(Error::custom)
         }
-        // If the database field is NULL, it will be None, so we return Ok(None)
-        None => Ok(None),
+        Some(serde_json::Value::Null) | None => Ok(None),
+        // An inline list, as written in an imported JSON file
+        Some(value) => serde_json::from_value(value).map(Some).map_err(Error::custom),
+    }
+}
+
+    // The inverse of `deserialize`: writes the list as a JSON string, the way
+    // the database stores it, so exports (including CSV, which can't hold
+    // nested lists) read back unchanged.
+pub fn serialize<S, T>(value: &Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    match value {
+        Some(items) => {
+            let s = serde_json::to_string(items).map_err(ser::Error::custom)?;
+            serializer.serialize_some(&s)
+        }
+        None => serializer.serialize_none(),
     }
 }